@@ -0,0 +1,156 @@
+//! Non-SI units accepted for use with the SI, from table 8 of the SI brochure.
+//!
+//! The litre, tonne, electronvolt and dalton take prefixes, eg `Milli<Litre>`, `Kilo<Tonne>` and
+//! `Mega<Electronvolt>`. The others do not. Units of time, length and mass convert exactly to their SI base
+//! unit with `base()`, while the rest convert through their quantity, eg `Joule::from(energy.quantity())`.
+//!
+//! None of them is an SI base unit, so none implements `Base`:
+//!
+//! ```compile_fail
+//! # extern crate si;
+//! # use si::accepted::Tonne;
+//! # use si::base::Base;
+//! fn base<B>(_: B) where B: Base {}
+//! # fn main() {
+//! base(Tonne::from(1));
+//! # }
+//! ```
+#[cfg(test)] use num::bigint::BigInt;
+#[cfg(test)] use num::pow::pow;
+use base::{gram, meter, second};
+use prefix::Kilo;
+#[cfg(test)] use base::*;
+#[cfg(test)] use derived::Joule;
+#[cfg(test)] use prefix::*;
+#[cfg(test)] use quantity::{Area, IntoQuantity, Volume};
+#[cfg(test)] use IntoBase;
+
+registered_units! {
+  generate_derived! {
+    name      = Minute,
+    module    = minute,
+    longform  = "minute",
+    shortform = "min",
+    dimension = Time,
+    factor    = (60, 1),
+    doc       = "A minute is a unit measuring time, equal to 60 s.",
+  }
+
+  generate_derived! {
+    name      = Hour,
+    module    = hour,
+    longform  = "hour",
+    shortform = "h",
+    dimension = Time,
+    factor    = (3_600, 1),
+    doc       = "An hour is a unit measuring time, equal to 3600 s.",
+  }
+
+  generate_derived! {
+    name      = Day,
+    module    = day,
+    longform  = "day",
+    shortform = "d",
+    dimension = Time,
+    factor    = (86_400, 1),
+    doc       = "A day is a unit measuring time, equal to 86400 s.",
+  }
+
+  generate_derived! {
+    name      = AstronomicalUnit,
+    module    = astronomical_unit,
+    longform  = "astronomical unit",
+    shortform = "au",
+    dimension = Length,
+    factor    = (149_597_870_700i64, 1),
+    doc       = "An astronomical unit is a unit measuring distance, equal to 149597870700 m.",
+  }
+
+  generate_derived! {
+    name      = Hectare,
+    module    = hectare,
+    longform  = "hectare",
+    shortform = "ha",
+    dimension = Area,
+    factor    = (10_000, 1),
+    doc       = "A hectare is a unit measuring area, equal to 10⁴ m².",
+  }
+
+  generate_derived! {
+    name      = Litre,
+    module    = litre,
+    longform  = "litre",
+    shortform = "L",
+    dimension = Volume,
+    factor    = (1, 1_000),
+    doc       = "A litre is a unit measuring volume, equal to 10⁻³ m³.",
+  }
+
+  generate_derived! {
+    name      = Tonne,
+    module    = tonne,
+    longform  = "tonne",
+    shortform = "t",
+    dimension = Mass,
+    factor    = (1_000, 1),
+    doc       = "A tonne is a unit measuring weight, equal to 10³ kg.",
+  }
+
+  generate_derived! {
+    name      = Electronvolt,
+    module    = electronvolt,
+    longform  = "electronvolt",
+    shortform = "eV",
+    dimension = Energy,
+    factor    = (1_602_176_634, ::num::pow::pow(::num::bigint::BigInt::from(10), 28)),
+    doc       = "An electronvolt is a unit measuring energy, equal to exactly 1.602176634×10⁻¹⁹ J.",
+  }
+
+  generate_derived! {
+    name      = Dalton,
+    module    = dalton,
+    longform  = "dalton",
+    shortform = "Da",
+    dimension = Mass,
+    factor    = (::constants::ATOMIC_MASS_DIGITS, ::num::pow::pow(::num::bigint::BigInt::from(10), ::constants::ATOMIC_MASS_EXPONENT)),
+    doc       = "A dalton is a unit measuring the mass of atoms, measured as 1.66053906892×10⁻²⁷ kg by CODATA 2022.",
+  }
+
+  prefixable! {
+    litre::Litre,
+    tonne::Tonne,
+    electronvolt::Electronvolt,
+    dalton::Dalton,
+  }
+}
+
+into_base! {
+  minute::Minute => second::Second<S>,
+  hour::Hour => second::Second<S>,
+  day::Day => second::Second<S>,
+  astronomical_unit::AstronomicalUnit => meter::Meter<S>,
+  tonne::Tonne => Kilo<gram::Gram<S>>,
+  dalton::Dalton => Kilo<gram::Gram<S>>,
+}
+
+#[test]
+fn converts_exactly_to_the_base() {
+  assert_eq!(IntoBase::<Second>::base(Hour::from(1)), Second::from(3_600));
+  assert_eq!(IntoBase::<Second>::base(Day::from(1) / 24), Second::from(3_600));
+  assert_eq!(IntoBase::<Kilogram>::base(Tonne::from(1)), Kilogram::from(1_000));
+  assert_eq!(IntoBase::<Meter>::base(AstronomicalUnit::from(1)), Meter::from(149_597_870_700i64));
+  assert_eq!(Litre::from(1).quantity(), Volume::from(1) / 1_000);
+  assert_eq!(Hectare::from(1).quantity(), Area::from(10_000));
+  assert_eq!(Joule::from(Mega::<Electronvolt>::from(1).quantity()), Joule::from(1_602_176_634) / pow(BigInt::from(10), 22));
+}
+
+#[test]
+fn takes_prefixes_where_allowed() {
+  assert_eq!(Milli::<Litre>::from(1_500), Litre::from(3) / 2);
+  assert_eq!(Kilo::<Tonne>::from(1).quantity(), Mega::<Gram>::from(1_000).quantity());
+  assert_eq!(Milli::<Second>::from(1) + Minute::from(1), Milli::<Second>::from(60_001));
+  assert_eq!("5 mL".parse::<Milli<Litre>>(), Ok(Milli::<Litre>::from(5)));
+  assert_eq!("3 kt".parse::<Kilo<Tonne>>(), Ok(Kilo::<Tonne>::from(3)));
+  assert_eq!(format!("{}", Kilo::<Dalton>::from(12)), "12 kDa");
+  assert_eq!(format!("{:#}", Hour::from(2)), "2 hours");
+}
@@ -1,79 +1,441 @@
 #[macro_use]
-macro_rules! base_from_primitives { 
+#[doc(hidden)]
+#[macro_export]
+macro_rules! base_from_primitives {
   {
-    $unit:ident, 
+    $unit:ident,
     [$($primitive:ty,)*]
   } => {
     $(
-      impl From<$primitive> for $unit {
+      impl<S> From<$primitive> for $unit<S> where S: Storage {
         fn from(value: $primitive) -> Self {
-          Self::from(BigInt::from(value))
+          Self::new($crate::storage::from_integer(value))
         }
       }
     )*
   }
 }
 
-#[macro_use]
-macro_rules! base_div_and_mul_with_primitives { 
+#[doc(hidden)]
+#[macro_export]
+macro_rules! base_from_floats {
+  {
+    $unit:ident,
+    [$($float:ty,)*]
+  } => {
+    $(
+      impl<S> ::std::convert::TryFrom<$float> for $unit<S> where S: Storage {
+        type Error = $crate::convert::ConversionError;
+        fn try_from(value: $float) -> Result<Self, Self::Error> {
+          $crate::convert::exact(value.into()).map(Self::from)
+        }
+      }
+    )*
+  }
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! base_into_integers {
   {
-    $unit:ident, 
+    $unit:ident,
     [$($primitive:ty,)*]
   } => {
     $(
-      impl Div<$primitive> for $unit {
-        type Output = Self;
-        fn div(self, value: $primitive) -> Self {
-          self / BigRational::from_integer(BigInt::from(value))
+      impl<S> ::std::convert::TryFrom<$unit<S>> for $primitive where S: Storage {
+        type Error = $crate::convert::ConversionError;
+        fn try_from(unit: $unit<S>) -> Result<Self, Self::Error> {
+          $crate::convert::integer(&unit.value)
         }
       }
+    )*
+  }
+}
 
-      impl Mul<$primitive> for $unit {
-        type Output = Self;
-        fn mul(self, value: $primitive) -> Self {
-          self * BigRational::from_integer(BigInt::from(value))
+#[doc(hidden)]
+#[macro_export]
+macro_rules! base_scalar_mul {
+  {
+    $unit:ident,
+    [$($scalar:ty,)*]
+  } => {
+    $(
+      impl<S> Mul<$unit<S>> for $scalar where S: Storage {
+        type Output = $unit<S>;
+        fn mul(self, unit: $unit<S>) -> $unit<S> {
+          unit * self
         }
       }
     )*
   }
 }
 
-#[macro_use]
-macro_rules! generate_base {
+// A quantity converts into a unit of its dimension, except into units of information, as information is
+// dimensionless in the SI and an angle or a ratio should not convert into bytes.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! base_from_quantity {
+  {
+    $name:ident,
+    false
+  } => {
+    impl<S> IntoBase<$name<S>> for QuantityOf<S> where S: Storage {
+      fn base(self) -> $name<S> {
+        $name::new(self.value().scale_by(&INVERSE_SCALE))
+      }
+    }
+
+    impl<S> From<QuantityOf<S>> for $name<S> where S: Storage {
+      fn from(val: QuantityOf<S>) -> Self {
+        val.base()
+      }
+    }
+  };
+  {
+    $name:ident,
+    true
+  } => {}
+}
+
+// Scalars of the unit's own storage also multiply on the left, eg `2.0 * Meter::<f64>::new(1.5)`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! base_storage_mul {
+  {
+    $unit:ident,
+    [$($storage:ty,)*]
+  } => {
+    $(
+      impl Mul<$unit<$storage>> for $storage {
+        type Output = $unit<$storage>;
+        fn mul(self, unit: $unit<$storage>) -> $unit<$storage> {
+          unit * self
+        }
+      }
+    )*
+  }
+}
+
+// Scalars in the storage of a unit multiply and divide its value directly, without converting to and from a
+// `BigRational`.
+macro_rules! operand_storage {
+  {
+    [$($storage:ty,)*]
+  } => {
+    $(
+      impl<B> Operand<B> for $storage where B: Base<Storage=$storage> {
+        type Product = B;
+        type Quotient = B;
+        fn multiply(self, unit: B) -> B {
+          B::new(unit.value() * self)
+        }
+        fn divide(self, unit: B) -> B {
+          B::new(unit.value() / self)
+        }
+      }
+    )*
+  }
+}
+
+// Scalars multiply and divide a base unit through `Operand`, scaling its value.
+macro_rules! operand_primitives {
+  {
+    [$($primitive:ty,)*]
+  } => {
+    $(
+      impl<B> Operand<B> for $primitive where B: Base {
+        type Product = B;
+        type Quotient = B;
+        fn multiply(self, unit: B) -> B {
+          B::new(unit.value() * ::storage::from_integer(self))
+        }
+        fn divide(self, unit: B) -> B {
+          B::new(unit.value() / ::storage::from_integer(self))
+        }
+      }
+    )*
+  }
+}
+
+// Serialize units exactly when the `serde` feature is on. The feature is checked here rather than where
+// `base_unit!` expands, which may be another crate.
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! base_serialization {
+  {
+    $name:ident
+  } => {
+    impl<S> $crate::serde::Serialize for $name<S> where S: Storage {
+      fn serialize<Z>(&self, serializer: Z) -> Result<Z::Ok, Z::Error> where Z: $crate::serde::Serializer {
+        $crate::serialization::exact::serialize(self, serializer)
+      }
+    }
+
+    impl<'de, S> $crate::serde::Deserialize<'de> for $name<S> where S: Storage {
+      fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: $crate::serde::Deserializer<'de> {
+        $crate::serialization::exact::deserialize(deserializer)
+      }
+    }
+  }
+}
+
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! base_serialization {
+  {
+    $name:ident
+  } => {}
+}
+
+// Convert units to a base unit of the same dimension through their quantity, eg an hour into seconds.
+macro_rules! into_base {
+  {
+    $($module:ident::$name:ident => $base:ty,)*
+  } => {
+    $(
+      impl<S> ::IntoBase<$base> for $module::$name<S> where S: ::storage::Storage {
+        fn base(self) -> $base {
+          ::IntoBase::<$base>::base(::quantity::IntoQuantity::quantity(self))
+        }
+      }
+    )*
+  }
+}
+
+// Define the units of a module, and `registered_units`, their entries in the registry. Base units take
+// prefixes, derived units take them once listed in `prefixable!`, and absolute temperatures, which are offset
+// from the kelvin, are left out.
+macro_rules! registered_units {
+  {
+    $($generate:ident! { $($body:tt)* })*
+  } => {
+    $($generate! { $($body)* })*
+
+    pub(crate) fn registered_units() -> Vec<::registry::UnitInfo> {
+      // A later entry for a unit, eg from `prefixable!`, replaces the one from its definition.
+      let mut units: Vec<::registry::UnitInfo> = Vec::new();
+      for unit in vec![$(registry_entries! { $generate { $($body)* } }),*].into_iter().flatten() {
+        match units.iter().position(|listed| listed.longform == unit.longform) {
+          Some(index) => units[index] = unit,
+          None => units.push(unit),
+        }
+      }
+      units
+    }
+  }
+}
+
+macro_rules! registry_entries {
+  {
+    generate_base { name = $name:ident, $($body:tt)* }
+  } => (
+    vec![::registry::UnitInfo::with_prefixes::<$name>()]
+  );
+  {
+    generate_derived { name = $name:ident, $($body:tt)* }
+  } => (
+    vec![::registry::UnitInfo::of::<$name>()]
+  );
+  {
+    prefixable { $($module:ident::$name:ident,)* }
+  } => (
+    vec![$(::registry::UnitInfo::with_prefixes::<$name>()),*]
+  );
+  {
+    $generate:ident { $($body:tt)* }
+  } => (
+    Vec::new()
+  );
+}
+
+/// Define a base unit, with the same arithmetic, prefixes, conversions, formatting and parsing as the units
+/// of this crate. It takes a marker trait for its dimension, which the unit implements, and what it
+/// measures: either `quantity`, the name of a quantity in `si::quantity`, or `exponents`, the `typenum`
+/// exponents of length, mass, time, current, temperature, amount and intensity as in `si::dimension::Dim`
+/// for a quantity without a name there. An optional `scale` (a power of ten) or `factor` (a fraction) gives
+/// its size in the coherent unit of the quantity.
+///
+/// Every unit measures a quantity of the SI dimensions, so a unit of a dimensionless quantity, eg counting
+/// pixels, would compare and convert with radians and every other dimensionless unit.
+///
+/// It generates the unit generic over its storage in a module named for the longform, and an alias for
+/// the unit storing a `BigRational`. Register the alias with `si::registry::register_prefixable` to look the
+/// unit up by name at runtime.
+///
+/// ```
+/// #[macro_use] extern crate si;
+/// use si::Unit;
+/// use si::base::Second;
+/// use si::derived::Pascal;
+/// use si::prefix::Centi;
+///
+/// pub trait Viscosity: Unit {}
+///
+/// base_unit! {
+///   name      = Poise,
+///   longform  = poise,
+///   shortform = P,
+///   dimension = Viscosity,
+///   exponents = (N1, P1, N1, Z0, Z0, Z0, Z0),
+///   scale     = -1,
+///   doc       = "A poise is a unit measuring dynamic viscosity, equal to 0.1 Pa·s.",
+/// }
+///
+/// fn main() {
+///   assert_eq!(Poise::from(10) / Pascal::from(1), Second::from(1));
+///   assert_eq!("2 cP".parse::<Poise>(), Ok(Centi::<Poise>::from(2).into()));
+///   assert_eq!(format!("{:#}", Poise::from(2)), "2 poises");
+/// }
+/// ```
+#[macro_export]
+macro_rules! base_unit {
   {
+    name      = $name:ident,
+    longform  = $longform:ident,
+    shortform = $shortform:ident,
+    dimension = $dimension:path,
+    quantity  = $quantity:ident,
+    $($size:tt)*
+  } => (
+    $crate::base_unit! {
+      @quantity = ($crate::quantity::$quantity),
+      name      = $name,
+      longform  = $longform,
+      shortform = $shortform,
+      dimension = $dimension,
+      $($size)*
+    }
+  );
+  {
+    name      = $name:ident,
+    longform  = $longform:ident,
+    shortform = $shortform:ident,
+    dimension = $dimension:path,
+    exponents = ($($exponent:ident),*),
+    $($size:tt)*
+  } => (
+    $crate::base_unit! {
+      @quantity = ($crate::quantity::Quantity<$crate::dimension::Dim<$($crate::typenum::$exponent),*>>),
+      name      = $name,
+      longform  = $longform,
+      shortform = $shortform,
+      dimension = $dimension,
+      $($size)*
+    }
+  );
+  {
+    @quantity = ($quantity:ty),
+    name      = $name:ident,
+    longform  = $longform:ident,
+    shortform = $shortform:ident,
+    dimension = $dimension:path,
+    $doc:meta,
+  } => (
+    $crate::base_unit! {
+      @quantity = ($quantity),
+      name      = $name,
+      longform  = $longform,
+      shortform = $shortform,
+      dimension = $dimension,
+      scale     = 0,
+      $doc,
+    }
+  );
+  {
+    @quantity = ($quantity:ty),
+    name      = $name:ident,
+    longform  = $longform:ident,
+    shortform = $shortform:ident,
+    dimension = $dimension:path,
+    scale     = $scale:expr,
+    $doc:meta,
+  } => (
+    $crate::base_unit! {
+      @size     = $crate::prefix::generate_prefix_factor($scale),
+      @marker   = ($dimension),
+      @information = false,
+      @tests    = {},
+      name      = $name,
+      longform  = $longform,
+      shortform = $shortform,
+      quantity  = ($quantity),
+      $doc,
+    }
+  );
+  {
+    @quantity = ($quantity:ty),
+    name      = $name:ident,
+    longform  = $longform:ident,
+    shortform = $shortform:ident,
+    dimension = $dimension:path,
+    factor    = ($numerator:expr, $denominator:expr),
+    $doc:meta,
+  } => (
+    $crate::base_unit! {
+      @size     = $crate::num::rational::BigRational::new(
+        $crate::num::bigint::BigInt::from($numerator), $crate::num::bigint::BigInt::from($denominator)),
+      @marker   = ($dimension),
+      @information = false,
+      @tests    = {},
+      name      = $name,
+      longform  = $longform,
+      shortform = $shortform,
+      quantity  = ($quantity),
+      $doc,
+    }
+  );
+  {
+    @size = $size:expr,
+    @marker = ($($marker:path),*),
+    @information = $information:tt,
+    @tests = { $($tests:tt)* },
     name   = $name:ident,
     longform = $longform:ident,
     shortform = $shortform:ident,
-    dimension = $dimension:ty,
+    quantity = ($quantity:ty),
     $doc:meta,
   } => (
-    mod $longform {
-      #[cfg(test)] use prefix::Kilo;
-      #[cfg(test)] use quickcheck::{Arbitrary, Gen};
-      use {Unit, IntoBase};
-      use base::Base;
-      use dimension::*;
-      use num::bigint::BigInt;
-      use num::rational::BigRational;
-      use prefix::Prefix;
+    #[doc = concat!("The ", stringify!($longform), ", generic over its numeric storage.")]
+    pub mod $longform {
+      use $crate::{Unit, IntoBase};
+      use $crate::base::{Base, Operand};
+      use $crate::num::bigint::BigInt;
+      use $crate::num::rational::BigRational;
+      use $crate::prefix::{is_fraction, Prefix, Prefixable};
+      use $crate::quantity::{Quantity, IntoQuantity, QuantityKind};
+      use $crate::format;
+      use $crate::parse::{self, ParseError};
       use std::cmp::*;
+      use std::fmt;
+      use std::iter::Sum;
       use std::ops::*;
+      use std::str::FromStr;
+      use $crate::storage::{Factor, Storage};
 
-      lazy_static! {
+      $crate::lazy_static::lazy_static! {
         static ref SHORTFORM: &'static str = stringify!($shortform);
         static ref LONGFORM: &'static str = stringify!($longform);
+        // The size of the unit in coherent units, eg 10^-3 for a gram or 8 for a byte.
+        static ref SCALE: Factor = Factor::new($size);
+        static ref INVERSE_SCALE: Factor = SCALE.recip();
       }
 
+      // The quantity of the same dimension and storage.
+      type QuantityOf<S> = Quantity<<$quantity as IntoQuantity>::Dimension, S>;
+
       #[$doc]
-      #[derive(Clone, Debug, Eq)]
-      pub struct $name {
-        value: BigRational,
+      #[derive(Clone, Debug)]
+      pub struct $name<S = BigRational> where S: Storage {
+        value: S,
       }
 
-      impl Unit for $name {
-        fn new(value: BigRational) -> Self {
-          $name { 
-            value: value 
+      impl<S> Unit for $name<S> where S: Storage {
+        type Storage = S;
+        fn new(value: S) -> Self {
+          $name {
+            value
           }
         }
         fn shortform() -> String {
@@ -82,233 +444,597 @@ macro_rules! generate_base {
         fn longform() -> String {
           (*LONGFORM).into()
         }
-        fn value(self) -> BigRational {
+        fn value(self) -> S {
           self.value
         }
-        fn value_ref(&self) -> &BigRational {
+        fn value_ref(&self) -> &S {
           &self.value
         }
       }
 
-      #[cfg(test)]
-      impl Arbitrary for $name {
-        fn arbitrary<G: Gen>(g: &mut G) -> Self {
-          let (numerator, denominator) = (g.gen::<i64>(), g.gen::<i64>());
-          let denominator = if denominator == 0 { 1 } else { denominator }; // The denominator cannot be zero.
-          
-          let (numerator, denominator) = (BigInt::from(numerator), BigInt::from(denominator));
-          let rational = BigRational::new(numerator, denominator);
-          Self::new(rational)
-        }
-      }
-
-      #[test]
-      fn has_right_shortform() {
-        assert_eq!($name::shortform(), stringify!($shortform))
-      }
-
-      #[test]
-      fn has_right_longform() {
-        assert_eq!($name::longform(), stringify!($longform))
-      }
-
       //
       // Markers
       //
-      impl $dimension for $name {}
+      impl<S> Prefixable for $name<S> where S: Storage {
+        fn takes_binary_prefixes() -> bool {
+          $information
+        }
+      }
 
-      impl Base for $name {}
+      impl<S> Base for $name<S> where S: Storage {}
 
-      impl IntoBase<$name> for $name {
+      impl<S> IntoBase<$name<S>> for $name<S> where S: Storage {
         fn base(self) -> Self {
           self
         }
       }
 
+      impl<S> IntoQuantity for $name<S> where S: Storage {
+        type Dimension = <$quantity as IntoQuantity>::Dimension;
+        fn quantity(self) -> QuantityOf<S> {
+          Quantity::new(self.value.scale_by(&SCALE))
+        }
+        fn quantity_kind() -> QuantityKind {
+          if $information { QuantityKind::Information } else { QuantityKind::Plain }
+        }
+      }
+
       //
       // Conversions
       //
-      impl From<BigInt> for $name {
+      impl<S> From<BigInt> for $name<S> where S: Storage {
         fn from(val: BigInt) -> Self {
-          let fraction = BigRational::from_integer(val); 
-          Self::new(fraction)
+          let fraction = BigRational::from_integer(val);
+          Self::from(fraction)
         }
       }
 
-      impl From<BigRational> for $name {
+      impl<S> From<BigRational> for $name<S> where S: Storage {
         fn from(val: BigRational) -> Self {
-          Self::new(val)
+          Self::new(S::from_rational(&val))
         }
       }
 
-      impl<P> From<P> for $name where P: Prefix<$name> {
+      impl<S,P> From<P> for $name<S> where S: Storage, P: Prefix<$name<S>> {
         fn from(val: P) -> Self {
           val.base()
         }
       }
 
-      base_from_primitives! { $name, [i64, u64, i32, u32, i16, u16, i8, u8, isize, usize,] }
+      impl<S> From<$name<S>> for QuantityOf<S> where S: Storage {
+        fn from(val: $name<S>) -> Self {
+          val.quantity()
+        }
+      }
+
+      $crate::base_from_quantity! { $name, $information }
+
+      $crate::base_from_primitives! { $name, [i64, u64, i32, u32, i16, u16, i8, u8, isize, usize,] }
+      $crate::base_from_floats! { $name, [f64, f32,] }
+      $crate::base_into_integers! { $name, [i64, u64, i32, u32, i16, u16, i8, u8, i128, u128, isize, usize,] }
 
       //
       // Operations on self
       //
-      impl Add for $name {
+      impl<S> Add for $name<S> where S: Storage {
         type Output = Self;
         fn add(self, other: Self) -> Self {
           Self::new(self.value + other.value())
         }
       }
 
-      #[cfg(test)]
-      quickcheck! {
-        fn can_add_self(first: $name, second: $name) -> bool {
-          let check = first.clone().value() + second.clone().value();
-          (first + second).value() == check
-        }
-      }
-
-      impl Sub for $name {
+      impl<S> Sub for $name<S> where S: Storage {
         type Output = Self;
         fn sub(self, other: Self) -> Self {
           Self::new(self.value - other.value())
         }
       }
 
-      #[cfg(test)]
-      quickcheck! {
-        fn can_sub_self(first: $name, second: $name) -> bool {
-          let check = first.clone().value() - second.clone().value();
-          (first - second).value() == check
+      //
+      // Operations on scalars and any dimension
+      //
+      impl<S,R> Div<R> for $name<S> where S: Storage, R: Operand<$name<S>> {
+        type Output = R::Quotient;
+        fn div(self, other: R) -> Self::Output {
+          other.divide(self)
+        }
+      }
+
+      impl<S,R> Mul<R> for $name<S> where S: Storage, R: Operand<$name<S>> {
+        type Output = R::Product;
+        fn mul(self, other: R) -> Self::Output {
+          other.multiply(self)
         }
       }
 
-      impl Div for $name {
+      //
+      // Operations on prefixes
+      //
+      // Prefixes below one, eg milli, measure both values in the prefix, so integer storage only truncates the
+      // result.
+      impl<S,P> Add<P> for $name<S> where S: Storage, P: Prefix<$name<S>> {
         type Output = Self;
-        fn div(self, other: Self) -> Self {
-          Self::new(self.value / other.value())
+        fn add(self, other: P) -> Self {
+          if is_fraction::<Self,P>() { (P::scale(self) + other).base() } else { self + other.base() }
         }
       }
 
-      #[cfg(test)]
-      quickcheck! {
-        fn can_div_self(first: $name, second: $name) -> bool {
-          let check = first.clone().value() / second.clone().value();
-          (first / second).value() == check
+      impl<S,P> Sub<P> for $name<S> where S: Storage, P: Prefix<$name<S>> {
+        type Output = Self;
+        fn sub(self, value: P) -> Self {
+          if is_fraction::<Self,P>() { (P::scale(self) - value).base() } else { self - value.base() }
         }
       }
 
-      impl Mul for $name {
+      //
+      // Negation and compound assignment
+      //
+      impl<S> Neg for $name<S> where S: Storage + Neg<Output=S> {
         type Output = Self;
-        fn mul(self, other: Self) -> Self {
-          Self::new(self.value * other.value())
+        fn neg(self) -> Self {
+          Self::new(-self.value)
         }
       }
 
-      #[cfg(test)]
-      quickcheck! {
-        fn can_mul_self(first: $name, second: $name) -> bool {
-          let check = first.clone().value() * second.clone().value();
-          (first * second).value() == check
+      impl<S,R> AddAssign<R> for $name<S> where S: Storage, $name<S>: Add<R, Output=$name<S>> {
+        fn add_assign(&mut self, other: R) {
+          *self = Self::new($crate::storage::take(&mut self.value)) + other;
+        }
+      }
+
+      impl<S,R> SubAssign<R> for $name<S> where S: Storage, $name<S>: Sub<R, Output=$name<S>> {
+        fn sub_assign(&mut self, other: R) {
+          *self = Self::new($crate::storage::take(&mut self.value)) - other;
+        }
+      }
+
+      impl<S,R> MulAssign<R> for $name<S> where S: Storage, $name<S>: Mul<R, Output=$name<S>> {
+        fn mul_assign(&mut self, other: R) {
+          *self = Self::new($crate::storage::take(&mut self.value)) * other;
+        }
+      }
+
+      impl<S,R> DivAssign<R> for $name<S> where S: Storage, $name<S>: Div<R, Output=$name<S>> {
+        fn div_assign(&mut self, other: R) {
+          *self = Self::new($crate::storage::take(&mut self.value)) / other;
         }
       }
 
       //
-      // Operations on prefixes
+      // Operations on references, as on values
       //
-      impl<P> Add<P> for $name where P: Prefix<$name> {
+      impl<'b,S> Add<&'b $name<S>> for $name<S> where S: Storage {
         type Output = Self;
-        fn add(self, other: P) -> Self {
-          self + other.base()
+        fn add(self, other: &'b $name<S>) -> Self {
+          Self::new(self.value + other.value.clone())
         }
       }
 
-      #[cfg(test)]
-      quickcheck! {
-        fn can_add_prefix(first: $name, second: Kilo<$name>) -> bool {
-          let check = first.clone().value() + second.clone().base().value();
-          (first + second).value() == check
+      impl<'b,S> Sub<&'b $name<S>> for $name<S> where S: Storage {
+        type Output = Self;
+        fn sub(self, other: &'b $name<S>) -> Self {
+          Self::new(self.value - other.value.clone())
         }
       }
 
-      impl<P> Sub<P> for $name where P: Prefix<$name> {
-        type Output = Self;
-        fn sub(self, value: P) -> Self {
-          self - value.base()
+      impl<'a,S> Neg for &'a $name<S> where S: Storage + Neg<Output=S> {
+        type Output = $name<S>;
+        fn neg(self) -> $name<S> {
+          -self.clone()
+        }
+      }
+
+      impl<'a,S,R> Add<R> for &'a $name<S> where S: Storage, $name<S>: Add<R> {
+        type Output = <$name<S> as Add<R>>::Output;
+        fn add(self, other: R) -> Self::Output {
+          self.clone() + other
         }
       }
 
-      #[cfg(test)]
-      quickcheck! {
-        fn can_sub_prefix(first: $name, second: Kilo<$name>) -> bool {
-          let check = first.clone().value() - second.clone().base().value();
-          (first - second).value() == check
+      impl<'a,S,R> Sub<R> for &'a $name<S> where S: Storage, $name<S>: Sub<R> {
+        type Output = <$name<S> as Sub<R>>::Output;
+        fn sub(self, other: R) -> Self::Output {
+          self.clone() - other
+        }
+      }
+
+      impl<'a,S,R> Mul<R> for &'a $name<S> where S: Storage, $name<S>: Mul<R> {
+        type Output = <$name<S> as Mul<R>>::Output;
+        fn mul(self, other: R) -> Self::Output {
+          self.clone() * other
+        }
+      }
+
+      impl<'a,S,R> Div<R> for &'a $name<S> where S: Storage, $name<S>: Div<R> {
+        type Output = <$name<S> as Div<R>>::Output;
+        fn div(self, other: R) -> Self::Output {
+          self.clone() / other
         }
       }
 
       //
-      // Dividing and multiplication are defined on integral types.
+      // Sums
       //
-      impl Div<BigRational> for $name {
-        type Output = Self;
-        fn div(self, value: BigRational) -> Self {
-          Self::from(self.value() / value)
+      impl<S> Sum for $name<S> where S: Storage {
+        fn sum<I>(iter: I) -> Self where I: Iterator<Item=Self> {
+          iter.fold(Self::new($crate::storage::zero()), Add::add)
         }
       }
 
-      impl Div<BigInt> for $name {
-        type Output = Self;
-        fn div(self, value: BigInt) -> Self {
-          Self::from(self.value() / BigRational::from_integer(value))
+      impl<'a,S> Sum<&'a $name<S>> for $name<S> where S: Storage + 'a {
+        fn sum<I>(iter: I) -> Self where I: Iterator<Item=&'a $name<S>> {
+          iter.fold(Self::new($crate::storage::zero()), Add::add)
         }
       }
 
-      impl Mul<BigRational> for $name {
-        type Output = Self;
-        fn mul(self, value: BigRational) -> Self {
-          Self::from(self.value() * value)
+      //
+      // Scalars multiply on either side.
+      //
+      $crate::base_scalar_mul! { $name, [BigRational, BigInt, i64, u64, i32, u32, i16, u16, i8, u8, isize, usize,] }
+      $crate::base_storage_mul! { $name, [f64, f32, $crate::num::rational::Ratio<i64>,] }
+
+      //
+      // Formatting
+      //
+      impl<S> fmt::Display for $name<S> where S: Storage {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+          format::display(self, f)
         }
       }
 
-      impl Mul<BigInt> for $name {
-        type Output = Self;
-        fn mul(self, value: BigInt) -> Self {
-          Self::from(self.value() * BigRational::from_integer(value))
+      impl<S> fmt::LowerExp for $name<S> where S: Storage {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+          format::scientific(self, f)
+        }
+      }
+
+      impl<S> fmt::UpperExp for $name<S> where S: Storage {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+          format::upper_scientific(self, f)
         }
       }
 
-      base_div_and_mul_with_primitives! { $name, [i64, u64, i32, u32, i16, u16, i8, u8, isize, usize,] }
+      //
+      // Serialization
+      //
+      $crate::base_serialization! { $name }
+
+      impl<S> FromStr for $name<S> where S: Storage {
+        type Err = ParseError;
+        fn from_str(input: &str) -> Result<Self, ParseError> {
+          parse::parse::<Self, Self>(input)
+        }
+      }
 
       //
       // Equals
       //
-      impl PartialEq for $name {
-        fn eq(&self, other: &$name) -> bool {
+      impl<S> PartialEq for $name<S> where S: Storage {
+        fn eq(&self, other: &Self) -> bool {
           self.value_ref() == other.value_ref()
         }
       }
 
-      #[cfg(test)]
-      quickcheck! {
-        fn can_eq_self(value: $name) -> bool {
-          let duplicate = value.clone();
-          value == duplicate
+      impl<S> Eq for $name<S> where S: Storage + Eq {}
+
+      impl<S,P> PartialEq<P> for $name<S> where S: Storage, P: Prefix<$name<S>> {
+        fn eq(&self, other: &P) -> bool {
+          if is_fraction::<Self,P>() {
+            P::scale(self.clone()).value_ref() == other.value_ref()
+          } else {
+            self.value_ref() == other.clone().base().value_ref()
+          }
         }
       }
 
-      impl<P> PartialEq<P> for $name where P: Prefix<$name> {
-        fn eq(&self, other: &P) -> bool {
-          self.value_ref() == other.clone().base().value_ref()
+      //
+      // Ordering
+      //
+      impl<S> PartialOrd for $name<S> where S: Storage {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+          self.value_ref().partial_cmp(other.value_ref())
         }
       }
 
-      #[cfg(test)]
-      quickcheck! {
-        fn can_eq_prefix(value: $name) -> bool {
-          let as_kilo = Kilo::scale(value.clone());
-          value == as_kilo
+      impl<S> Ord for $name<S> where S: Storage + Ord {
+        fn cmp(&self, other: &Self) -> Ordering {
+          self.value_ref().cmp(other.value_ref())
         }
       }
+
+      impl<S,P> PartialOrd<P> for $name<S> where S: Storage, P: Prefix<$name<S>> {
+        fn partial_cmp(&self, other: &P) -> Option<Ordering> {
+          if is_fraction::<Self,P>() {
+            P::scale(self.clone()).value_ref().partial_cmp(other.value_ref())
+          } else {
+            self.value_ref().partial_cmp(other.clone().base().value_ref())
+          }
+        }
+      }
+
+      $($tests)*
+    }
+
+    $(impl<S> $marker for self::$longform::$name<S> where S: $crate::storage::Storage {})*
+
+    #[$doc]
+    pub type $name = self::$longform::$name<$crate::num::rational::BigRational>;
+  )
+}
+
+// The tests of converting a unit into its quantity and back, for units that a quantity converts into.
+macro_rules! base_quantity_tests {
+  {
+    $name:ident,
+    $quantity:ident
+  } => (
+    #[cfg(test)]
+    quickcheck! {
+      fn into_quantity_and_back_is_equal(value: $name) -> bool {
+        let quantity = ::quantity::$quantity::from(value.clone());
+        <$name>::from(quantity).value() == value.value()
+      }
+    }
+  )
+}
+
+// The tests of each unit of this crate, which `generate_base!` puts in the unit's module.
+macro_rules! base_tests {
+  {
+    $name:ident,
+    $quantity:ident,
+    $longform:ident,
+    $shortform:ident
+  } => (
+    #[cfg(test)] use prefix::Kilo;
+    #[cfg(test)] use quickcheck::{Arbitrary, Gen};
+
+    #[cfg(test)]
+    impl Arbitrary for $name {
+      fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        let (numerator, denominator) = (g.gen::<i64>(), g.gen::<i64>());
+        let denominator = if denominator == 0 { 1 } else { denominator }; // The denominator cannot be zero.
+
+        let (numerator, denominator) = (BigInt::from(numerator), BigInt::from(denominator));
+        let rational = BigRational::new(numerator, denominator);
+        Self::new(rational)
+      }
+    }
+
+    #[test]
+    fn has_right_shortform() {
+      assert_eq!(<$name>::shortform(), stringify!($shortform))
+    }
+
+    #[test]
+    fn has_right_longform() {
+      assert_eq!(<$name>::longform(), stringify!($longform))
+    }
+
+    //
+    // Operations on self
+    //
+    #[cfg(test)]
+    quickcheck! {
+      fn can_add_self(first: $name, second: $name) -> bool {
+        let check = first.clone().value() + second.clone().value();
+        (first + second).value() == check
+      }
+      fn can_sub_self(first: $name, second: $name) -> bool {
+        let check = first.clone().value() - second.clone().value();
+        (first - second).value() == check
+      }
+    }
+
+    //
+    // Operations on any dimension
+    //
+    #[cfg(test)]
+    quickcheck! {
+      fn can_div_self(first: $name, second: $name) -> bool {
+        let check = first.clone().quantity().value() / second.clone().quantity().value();
+        (first / second).value() == check
+      }
+      fn can_div_prefix(first: $name, second: Kilo<$name>) -> bool {
+        let check = first.clone().quantity().value() / second.clone().quantity().value();
+        (first / second).value() == check
+      }
+      fn can_mul_self(first: $name, second: $name) -> bool {
+        let check = first.clone().quantity().value() * second.clone().quantity().value();
+        (first * second).value() == check
+      }
+      fn can_mul_prefix(first: $name, second: Kilo<$name>) -> bool {
+        let check = first.clone().quantity().value() * second.clone().quantity().value();
+        (first * second).value() == check
+      }
+    }
+
+    //
+    // Operations on prefixes
+    //
+    #[cfg(test)]
+    quickcheck! {
+      fn can_add_prefix(first: $name, second: Kilo<$name>) -> bool {
+        let check = first.clone().value() + IntoBase::<$name>::base(second.clone()).value();
+        (first + second).value() == check
+      }
+      fn can_sub_prefix(first: $name, second: Kilo<$name>) -> bool {
+        let check = first.clone().value() - IntoBase::<$name>::base(second.clone()).value();
+        (first - second).value() == check
+      }
+    }
+
+    //
+    // Negation, assignment and references
+    //
+    #[cfg(test)]
+    quickcheck! {
+      fn can_neg(value: $name) -> bool {
+        (-value.clone()).value() == -value.value()
+      }
+      fn can_assign(first: $name, second: Kilo<$name>) -> bool {
+        let mut value = first.clone();
+        value += second.clone();
+        value -= &first;
+        value *= 4;
+        value /= 2;
+        value == IntoBase::<$name>::base(second) * 2
+      }
+      fn can_operate_on_references(first: $name, second: $name) -> bool {
+        let sum = &first + &second;
+        let product = &first * second.clone();
+        sum == first.clone() + second.clone() && &first - &second == -(&second - &first) &&
+          &first * 3 == 3 * first.clone() && product.value() == (first * second).value()
+      }
+    }
+
+    #[cfg(test)]
+    quickcheck! {
+      fn can_sum(values: Vec<i64>) -> bool {
+        let check = <$name>::from(values.iter().map(|&value| BigInt::from(value)).sum::<BigInt>());
+        let values: Vec<$name> = values.into_iter().map(<$name>::from).collect();
+        values.iter().sum::<$name>() == check && values.into_iter().sum::<$name>() == check
+      }
+    }
+
+    //
+    // Formatting and parsing
+    //
+    #[test]
+    fn can_display() {
+      let value = <$name>::from(3) / 2;
+      assert_eq!(format!("{}", value), format!("1.5 {}", <$name>::shortform()));
+      assert_eq!(format!("{:#}", value), format!("1.5 {}", format::plural(&<$name>::longform())));
+      assert_eq!(format!("{:.3e}", value * 1000), format!("1.500e3 {}", <$name>::shortform()));
+    }
+
+    #[cfg(test)]
+    quickcheck! {
+      fn can_parse_display(value: $name) -> bool {
+        value.to_string().parse::<$name>() == Ok(value)
+      }
+    }
+
+    #[test]
+    fn can_parse_prefix() {
+      let input = format!("3/2 k{}", <$name>::shortform());
+      assert_eq!(input.parse::<$name>(), Ok(<$name>::from(1_500)));
+      let input = format!("1.5e3 {}", format::plural(&<$name>::longform()));
+      assert_eq!(input.parse::<$name>(), Ok(<$name>::from(1_500)));
+      assert!("1 furlong".parse::<$name>().is_err());
+    }
+
+    //
+    // Equals and ordering
+    //
+    #[cfg(test)]
+    quickcheck! {
+      fn can_eq_self(value: $name) -> bool {
+        let duplicate = value.clone();
+        value == duplicate
+      }
+      fn can_eq_prefix(value: $name) -> bool {
+        let as_kilo = Kilo::scale(value.clone());
+        value == as_kilo
+      }
+      fn can_order_self(first: $name, second: $name) -> bool {
+        first.cmp(&second) == first.value_ref().cmp(second.value_ref())
+      }
+      fn can_order_prefix(first: $name, second: Kilo<$name>) -> bool {
+        let check = first.value_ref().partial_cmp(IntoBase::<$name>::base(second.clone()).value_ref());
+        first.partial_cmp(&second) == check
+      }
+    }
+
+    //
+    // Storage
+    //
+    #[test]
+    fn can_use_other_storage() {
+      let value = $name::<f64>::new(1_000.0) + $name::<f64>::from(500);
+      assert_eq!(value.clone().value(), 1_500.0);
+      assert_eq!(Kilo::<$name<f64>>::from(value.clone()).value(), 1.5);
+      assert_eq!(format!("{}", value), format!("1500 {}", <$name>::shortform()));
+      assert_eq!(Kilo::<$name<i64>>::from($name::<i64>::from(1_500)).value(), 1);
     }
-    pub use self::$longform::$name;
   )
 }
+
+// The base units of this crate, and units of information, which are `base_unit!` with their tests. The
+// dimension names both the marker trait in `dimension` and the quantity.
+#[macro_use]
+macro_rules! generate_base {
+  // Units of information take the binary prefixes, and no quantity converts into them.
+  {
+    name   = $name:ident,
+    longform = $longform:ident,
+    shortform = $shortform:ident,
+    dimension = Information,
+    $doc:meta,
+  } => (
+    generate_base! {
+      name      = $name,
+      longform  = $longform,
+      shortform = $shortform,
+      dimension = Information,
+      factor    = (1, 1),
+      $doc,
+    }
+  );
+  {
+    name   = $name:ident,
+    longform = $longform:ident,
+    shortform = $shortform:ident,
+    dimension = Information,
+    factor = ($numerator:expr, $denominator:expr),
+    $doc:meta,
+  } => (
+    base_unit! {
+      @size     = ::num::rational::BigRational::new(::num::bigint::BigInt::from($numerator), ::num::bigint::BigInt::from($denominator)),
+      @marker   = (::dimension::Information),
+      @information = true,
+      @tests    = { base_tests! { $name, Information, $longform, $shortform } },
+      name      = $name,
+      longform  = $longform,
+      shortform = $shortform,
+      quantity  = (::quantity::Information),
+      $doc,
+    }
+  );
+  {
+    name   = $name:ident,
+    longform = $longform:ident,
+    shortform = $shortform:ident,
+    dimension = $dimension:ident,
+    $doc:meta,
+  } => (
+    generate_base! {
+      name      = $name,
+      longform  = $longform,
+      shortform = $shortform,
+      dimension = $dimension,
+      scale     = 0,
+      $doc,
+    }
+  );
+  {
+    name   = $name:ident,
+    longform = $longform:ident,
+    shortform = $shortform:ident,
+    dimension = $dimension:ident,
+    scale = $scale:expr,
+    $doc:meta,
+  } => (
+    base_unit! {
+      @size     = ::prefix::generate_prefix_factor($scale),
+      @marker   = (::dimension::$dimension),
+      @information = false,
+      @tests    = { base_tests! { $name, $dimension, $longform, $shortform } base_quantity_tests! { $name, $dimension } },
+      name      = $name,
+      longform  = $longform,
+      shortform = $shortform,
+      quantity  = (::quantity::$dimension),
+      $doc,
+    }
+  );
+}
@@ -1,61 +1,133 @@
-use Unit;
+use std::ops::{Div, Mul};
+use num::bigint::BigInt;
+use num::rational::{BigRational, Ratio};
+use {Unit, IntoBase};
+use prefix::{Kilo, Prefixable};
+use quantity::{IntoQuantity, Mass, Quantity};
+use storage::Storage;
 
 #[macro_use] mod macros;
 
-pub trait Base: Unit {}
+pub trait Base: Prefixable {}
 
-generate_base! {
-  name      = Meter,
-  longform  = meter,
-  shortform = m,
-  dimension = Length,
-  doc       = "A meter is a unit measuring distance.", 
+/// The right hand side of multiplying or dividing a base unit. A scalar scales the unit, while another unit
+/// gives a quantity. Having one trait for both lets `base_unit!` define the operations in other crates.
+#[doc(hidden)]
+pub trait Operand<B>: Sized where B: Base {
+  /// The result of `unit * self`.
+  type Product;
+  /// The result of `unit / self`.
+  type Quotient;
+  fn multiply(self, unit: B) -> Self::Product;
+  fn divide(self, unit: B) -> Self::Quotient;
 }
 
-generate_base! {
-  name      = Gram,
-  longform  = gram,
-  shortform = g,
-  dimension = Mass,
-  doc       = "A gram is a unit measuring weight.", 
+impl<B> Operand<B> for BigRational where B: Base {
+  type Product = B;
+  type Quotient = B;
+  fn multiply(self, unit: B) -> B {
+    B::new(unit.value().scale(&self))
+  }
+  fn divide(self, unit: B) -> B {
+    B::new(unit.value().scale(&self.recip()))
+  }
 }
 
-generate_base! {
-  name      = Second,
-  longform  = second,
-  shortform = s,
-  dimension = Time,
-  doc       = "A second is a unit measuring time.", 
+impl<B> Operand<B> for BigInt where B: Base {
+  type Product = B;
+  type Quotient = B;
+  fn multiply(self, unit: B) -> B {
+    BigRational::from_integer(self).multiply(unit)
+  }
+  fn divide(self, unit: B) -> B {
+    BigRational::from_integer(self).divide(unit)
+  }
 }
 
-generate_base! {
-  name      = Ampere,
-  longform  = ampere,
-  shortform = A,
-  dimension = Current,
-  doc       = "An ampere is a unit measuring electrical current.", 
+operand_primitives! { [i64, u64, i32, u32, i16, u16, i8, u8, isize, usize,] }
+operand_storage! { [f64, f32, Ratio<i64>,] }
+
+impl<B,U> Operand<B> for U
+where B: Base, U: IntoQuantity, Quantity<B::Dimension, B::Storage>: Mul<U> + Div<U> {
+  type Product = <Quantity<B::Dimension, B::Storage> as Mul<U>>::Output;
+  type Quotient = <Quantity<B::Dimension, B::Storage> as Div<U>>::Output;
+  fn multiply(self, unit: B) -> Self::Product {
+    unit.quantity() * self
+  }
+  fn divide(self, unit: B) -> Self::Quotient {
+    unit.quantity() / self
+  }
 }
 
-generate_base! {
-  name      = Kelvin,
-  longform  = kelvin,
-  shortform = K,
-  dimension = Temperature,
-  doc       = "A kelvin is a unit measuring thermodynamic temperature.", 
+registered_units! {
+  generate_base! {
+    name      = Meter,
+    longform  = meter,
+    shortform = m,
+    dimension = Length,
+    doc       = "A meter is a unit measuring distance.", 
+  }
+
+  generate_base! {
+    name      = Gram,
+    longform  = gram,
+    shortform = g,
+    dimension = Mass,
+    scale     = -3,
+    doc       = "A gram is a unit measuring weight. Prefixes apply to the gram, but the SI base unit of mass is the kilogram.", 
+  }
+
+  generate_base! {
+    name      = Second,
+    longform  = second,
+    shortform = s,
+    dimension = Time,
+    doc       = "A second is a unit measuring time.", 
+  }
+
+  generate_base! {
+    name      = Ampere,
+    longform  = ampere,
+    shortform = A,
+    dimension = Current,
+    doc       = "An ampere is a unit measuring electrical current.", 
+  }
+
+  generate_base! {
+    name      = Kelvin,
+    longform  = kelvin,
+    shortform = K,
+    dimension = Temperature,
+    doc       = "A kelvin is a unit measuring thermodynamic temperature.", 
+  }
+
+  generate_base! {
+    name      = Mole,
+    longform  = mole,
+    shortform = mol,
+    dimension = Amount,
+    doc       = "A mole is a unit measuring the amount of a substance.", 
+  }
+
+  generate_base! {
+    name      = Candela,
+    longform  = candela,
+    shortform = cd,
+    dimension = Intensity,
+    doc       = "A candela is a unit measuring the amount of luminous intensity.", 
+  }
 }
+/// A kilogram is the SI base unit of mass. It is the same type as `Kilo<Gram>`.
+pub type Kilogram = Kilo<Gram>;
 
-generate_base! {
-  name      = Mole,
-  longform  = mole,
-  shortform = mol,
-  dimension = Amount,
-  doc       = "A mole is a unit measuring the amount of a substance.", 
+impl<S> IntoBase<Kilo<gram::Gram<S>>> for gram::Gram<S> where S: Storage {
+  fn base(self) -> Kilo<gram::Gram<S>> {
+    Kilo::from(self)
+  }
 }
 
-generate_base! {
-  name      = Candela,
-  longform  = candela,
-  shortform = cd,
-  dimension = Intensity,
-  doc       = "A candela is a unit measuring the amount of luminous intensity.", 
-}
\ No newline at end of file
+impl<S> IntoBase<Kilo<gram::Gram<S>>> for Quantity<<Mass as IntoQuantity>::Dimension, S> where S: Storage {
+  fn base(self) -> Kilo<gram::Gram<S>> {
+    Kilo::new(self.value())
+  }
+}
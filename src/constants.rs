@@ -0,0 +1,227 @@
+//! Physical constants as quantities in coherent SI units.
+//!
+//! The seven defining constants fix the SI units exactly since 2019, so they and the constants derived from
+//! them by multiplication and division, eg the Faraday constant, are exact. Constants involving π, such as
+//! the reduced Planck constant, are not rational and are rounded to 40 significant digits. Measured constants
+//! are `Measured` values from CODATA 2022, with their standard uncertainty.
+use num::bigint::BigInt;
+use num::pow::pow;
+use num::rational::BigRational;
+use typenum::{Z0, P1, P2, P3, P4, N1, N2, N3, N4};
+use convert::significant_digits;
+use dimension::{Dimension, Dim};
+use parse::parse_number;
+use quantity::*;
+use Unit;
+#[cfg(test)] use accepted::Dalton;
+#[cfg(test)] use base::*;
+#[cfg(test)] use num::ToPrimitive;
+
+/// A quantity of action, in joule seconds.
+pub type Action = Quantity<Dim<P2, P1, N1, Z0, Z0, Z0, Z0>>;
+/// A quantity of entropy or heat capacity, in joules per kelvin.
+pub type Entropy = Quantity<Dim<P2, P1, N2, Z0, N1, Z0, Z0>>;
+/// A quantity of molar entropy or molar heat capacity, in joules per kelvin mole.
+pub type MolarEntropy = Quantity<Dim<P2, P1, N2, Z0, N1, N1, Z0>>;
+/// A quantity of charge per amount of substance, in coulombs per mole.
+pub type MolarCharge = Quantity<Dim<Z0, Z0, P1, P1, Z0, N1, Z0>>;
+/// A quantity per amount of substance, in reciprocal moles.
+pub type ReciprocalAmount = Quantity<Dim<Z0, Z0, Z0, Z0, Z0, N1, Z0>>;
+/// A quantity of luminous efficacy, in lumens per watt.
+pub type LuminousEfficacy = Quantity<Dim<N2, N1, P3, Z0, Z0, Z0, P1>>;
+/// A quantity of magnetic permeability, in henries per meter.
+pub type Permeability = Quantity<Dim<P1, P1, N2, N2, Z0, Z0, Z0>>;
+/// A quantity of electric permittivity, in farads per meter.
+pub type Permittivity = Quantity<Dim<N3, N1, P4, P2, Z0, Z0, Z0>>;
+/// A quantity of wavenumber, in reciprocal meters.
+pub type Wavenumber = Quantity<Dim<N1, Z0, Z0, Z0, Z0, Z0, Z0>>;
+
+/// A measured constant, with the standard uncertainty of its value.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Measured<D> where D: Dimension {
+  /// The recommended value.
+  pub value: Quantity<D>,
+  /// The standard uncertainty, one standard deviation of the value.
+  pub uncertainty: Quantity<D>,
+}
+
+impl<D> Measured<D> where D: Dimension {
+  /// The uncertainty relative to the value.
+  pub fn relative_uncertainty(&self) -> BigRational {
+    self.uncertainty.value_ref() / self.value.value_ref()
+  }
+}
+
+// π to 50 decimal places, far beyond the digits kept of the constants using it.
+const PI: &str = "3.14159265358979323846264338327950288419716939937510";
+
+// The digits kept of constants that are not rational.
+const DIGITS: usize = 40;
+
+// The atomic mass constant in kilograms, these digits over 10³⁸, which the dalton shares.
+pub(crate) const ATOMIC_MASS_DIGITS: i64 = 166_053_906_892;
+pub(crate) const ATOMIC_MASS_EXPONENT: usize = 38;
+
+fn exact<D>(value: &str) -> Quantity<D> where D: Dimension {
+  Quantity::from(parse_number(value).expect("constants are valid numbers"))
+}
+
+fn measured<D>(value: &str, uncertainty: &str) -> Measured<D> where D: Dimension {
+  Measured { value: exact(value), uncertainty: exact(uncertainty) }
+}
+
+fn pi() -> BigRational {
+  parse_number(PI).expect("π is a valid number")
+}
+
+//
+// Defining constants
+//
+
+/// The hyperfine transition frequency of caesium 133, ΔνCs, exactly 9192631770 Hz.
+pub fn hyperfine_transition_frequency() -> Frequency {
+  exact("9192631770")
+}
+
+/// The speed of light in vacuum, c, exactly 299792458 m/s.
+pub fn speed_of_light() -> Velocity {
+  exact("299792458")
+}
+
+/// The Planck constant, h, exactly 6.62607015e-34 J·s.
+pub fn planck_constant() -> Action {
+  exact("6.62607015e-34")
+}
+
+/// The elementary charge, e, exactly 1.602176634e-19 C.
+pub fn elementary_charge() -> Charge {
+  exact("1.602176634e-19")
+}
+
+/// The Boltzmann constant, k, exactly 1.380649e-23 J/K.
+pub fn boltzmann_constant() -> Entropy {
+  exact("1.380649e-23")
+}
+
+/// The Avogadro constant, NA, exactly 6.02214076e23 mol⁻¹.
+pub fn avogadro_constant() -> ReciprocalAmount {
+  exact("6.02214076e23")
+}
+
+/// The luminous efficacy of monochromatic radiation of 540 THz, Kcd, exactly 683 lm/W.
+pub fn luminous_efficacy() -> LuminousEfficacy {
+  exact("683")
+}
+
+//
+// Derived constants
+//
+
+/// The Faraday constant, F = e·NA, exactly 96485.3321233100184 C/mol.
+pub fn faraday_constant() -> MolarCharge {
+  elementary_charge() * avogadro_constant()
+}
+
+/// The molar gas constant, R = k·NA, exactly 8.31446261815324 J/(mol·K).
+pub fn molar_gas_constant() -> MolarEntropy {
+  boltzmann_constant() * avogadro_constant()
+}
+
+/// The reduced Planck constant, ħ = h / 2π, about 1.054571817e-34 J·s. It is not rational, so this is
+/// rounded to 40 significant digits.
+pub fn reduced_planck_constant() -> Action {
+  let value = planck_constant().value() / (pi() * BigRational::from_integer(2.into()));
+  Quantity::from(significant_digits(value, DIGITS))
+}
+
+/// The Stefan–Boltzmann constant, σ = 2π⁵k⁴ / 15h³c², about 5.670374419e-8 W/(m²·K⁴). It is not
+/// rational, so this is rounded to 40 significant digits.
+pub fn stefan_boltzmann_constant() -> Quantity<Dim<Z0, P1, N3, Z0, N4, Z0, Z0>> {
+  let k = boltzmann_constant() * boltzmann_constant();
+  let h = planck_constant() * planck_constant() * planck_constant();
+  let c = speed_of_light() * speed_of_light();
+  let value = (k.clone() * k / (h * c)).value() * pi().pow(5) * BigRational::new(2.into(), 15.into());
+  Quantity::from(significant_digits(value, DIGITS))
+}
+
+//
+// Measured constants
+//
+
+/// The Newtonian constant of gravitation, G, 6.67430(15)e-11 m³/(kg·s²).
+pub fn gravitational_constant() -> Measured<Dim<P3, N1, N2, Z0, Z0, Z0, Z0>> {
+  measured("6.67430e-11", "0.00015e-11")
+}
+
+/// The electron mass, 9.1093837139(28)e-31 kg.
+pub fn electron_mass() -> Measured<Dim<Z0, P1, Z0, Z0, Z0, Z0, Z0>> {
+  measured("9.1093837139e-31", "0.0000000028e-31")
+}
+
+/// The proton mass, 1.67262192595(52)e-27 kg.
+pub fn proton_mass() -> Measured<Dim<Z0, P1, Z0, Z0, Z0, Z0, Z0>> {
+  measured("1.67262192595e-27", "0.00000000052e-27")
+}
+
+/// The neutron mass, 1.67492750056(85)e-27 kg.
+pub fn neutron_mass() -> Measured<Dim<Z0, P1, Z0, Z0, Z0, Z0, Z0>> {
+  measured("1.67492750056e-27", "0.00000000085e-27")
+}
+
+/// The atomic mass constant, mu, 1.66053906892(52)e-27 kg.
+pub fn atomic_mass_constant() -> Measured<Dim<Z0, P1, Z0, Z0, Z0, Z0, Z0>> {
+  let value = BigRational::new(ATOMIC_MASS_DIGITS.into(), pow(BigInt::from(10), ATOMIC_MASS_EXPONENT));
+  Measured { value: Quantity::from(value), uncertainty: exact("0.00000000052e-27") }
+}
+
+/// The fine-structure constant, α, 7.2973525643(11)e-3.
+pub fn fine_structure_constant() -> Measured<Dim<Z0, Z0, Z0, Z0, Z0, Z0, Z0>> {
+  measured("7.2973525643e-3", "0.0000000011e-3")
+}
+
+/// The vacuum magnetic permeability, μ0, 1.25663706127(20)e-6 H/m.
+pub fn vacuum_permeability() -> Measured<Dim<P1, P1, N2, N2, Z0, Z0, Z0>> {
+  measured("1.25663706127e-6", "0.00000000020e-6")
+}
+
+/// The vacuum electric permittivity, ε0, 8.8541878188(14)e-12 F/m.
+pub fn vacuum_permittivity() -> Measured<Dim<N3, N1, P4, P2, Z0, Z0, Z0>> {
+  measured("8.8541878188e-12", "0.0000000014e-12")
+}
+
+/// The Rydberg constant, R∞, 10973731.568157(12) m⁻¹.
+pub fn rydberg_constant() -> Measured<Dim<N1, Z0, Z0, Z0, Z0, Z0, Z0>> {
+  measured("10973731.568157", "0.000012")
+}
+
+#[test]
+fn defining_constants_are_exact() {
+  assert_eq!(speed_of_light(), Meter::from(299_792_458) / Second::from(1));
+  assert_eq!(hyperfine_transition_frequency() * Second::from(1), Dimensionless::from(9_192_631_770i64));
+  assert_eq!(elementary_charge() * avogadro_constant(), exact::<Dim<Z0, Z0, P1, P1, Z0, N1, Z0>>("96485.3321233100184"));
+  assert_eq!(molar_gas_constant(), exact::<Dim<P2, P1, N2, Z0, N1, N1, Z0>>("8.31446261815324"));
+}
+
+#[test]
+fn irrational_constants_are_close() {
+  let relative = |value: f64, expected: f64| ((value - expected) / expected).abs();
+  assert!(relative(reduced_planck_constant().to_f64().unwrap(), 1.054_571_817e-34) < 1e-9);
+  assert!(relative(stefan_boltzmann_constant().to_f64().unwrap(), 5.670_374_419e-8) < 1e-9);
+  let hbar = reduced_planck_constant().value();
+  assert!(*hbar.denom() <= parse_number("1e74").unwrap().to_integer());
+}
+
+#[test]
+fn measured_constants_are_consistent() {
+  assert!(gravitational_constant().relative_uncertainty() < parse_number("3e-5").unwrap());
+  // μ0·ε0·c² is one within the uncertainty of the measurements.
+  let one = vacuum_permeability().value * vacuum_permittivity().value * speed_of_light() * speed_of_light();
+  let error = (one.value() - BigRational::from_integer(1.into())).to_f64().unwrap().abs();
+  assert!(error < 1e-9);
+}
+
+#[test]
+fn dalton_is_the_atomic_mass_constant() {
+  assert_eq!(Dalton::from(1).quantity(), atomic_mass_constant().value);
+  assert_eq!(atomic_mass_constant().value, exact("1.66053906892e-27"));
+}
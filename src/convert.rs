@@ -0,0 +1,269 @@
+//! Conversion of units into and from floating point and integer primitives, and rounding.
+//!
+//! Units convert from an `f64` or `f32` with `TryFrom`, taking its exact binary value, so `0.1` is slightly
+//! more than a tenth. `Unit::from_decimal` reads a decimal exactly instead, so `"0.1"` is exactly 1/10.
+//!
+//! `Unit::to_f64` and `Unit::to_f32` give the nearest float. Converting into an integer with `TryFrom` is
+//! exact, failing unless the value is a whole number in range, while `Unit::round` and `Unit::to_integer`
+//! round by an explicit `Rounding`. Eg a length in whole millimeters is
+//! `Milli::<Meter>::from(length).round(Rounding::HalfEven)`.
+//!
+//! Long chains of exact arithmetic can grow the numerator and denominator without bound. `Unit::approximate`,
+//! `Unit::round_to` and `Unit::limit_significant_digits` bound them again, keeping the unit.
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::mem;
+use num::{Integer, One, Signed, ToPrimitive, Zero};
+use num::bigint::BigInt;
+use num::pow::pow;
+use num::rational::BigRational;
+use storage::Storage;
+use Unit;
+#[cfg(test)] use base::*;
+#[cfg(test)] use prefix::*;
+
+/// How to round a value to an integer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Rounding {
+  /// Towards negative infinity, so -1.5 is -2.
+  Floor,
+  /// Towards positive infinity, so -1.5 is -1.
+  Ceil,
+  /// To the nearest integer, and halves to the even one, so 0.5 is 0 and 1.5 is 2.
+  HalfEven,
+  /// Towards zero, dropping any fraction, so -1.5 is -1.
+  TowardZero,
+}
+
+impl Rounding {
+  /// Round the value to an integer.
+  pub fn round(self, value: &BigRational) -> BigInt {
+    match self {
+      Rounding::Floor => value.floor().to_integer(),
+      Rounding::Ceil => value.ceil().to_integer(),
+      Rounding::TowardZero => value.trunc().to_integer(),
+      Rounding::HalfEven => {
+        let floor = value.floor().to_integer();
+        let remainder = value - BigRational::from_integer(floor.clone());
+        let half = BigRational::new(BigInt::one(), BigInt::from(2));
+        if remainder > half || (remainder == half && floor.is_odd()) {
+          floor + BigInt::one()
+        } else {
+          floor
+        }
+      },
+    }
+  }
+}
+
+// Apply an exact operation to the value of a unit, leaving values that are not finite numbers unchanged.
+pub(crate) fn map<U,F>(unit: U, operation: F) -> U where U: Unit, F: FnOnce(BigRational) -> BigRational {
+  match unit.value_ref().to_rational() {
+    Some(value) => U::new(U::Storage::from_rational(&operation(value))),
+    None => unit,
+  }
+}
+
+// The closest value with a denominator of at most `max`, from the convergents of its continued fraction and
+// the semiconvergent between the last two.
+pub(crate) fn approximate(value: BigRational, max: &BigInt) -> BigRational {
+  assert!(*max >= BigInt::one(), "the maximum denominator must be at least one");
+  if value.denom() <= max {
+    return value;
+  }
+  let (mut p0, mut q0, mut p1, mut q1) = (BigInt::zero(), BigInt::one(), BigInt::one(), BigInt::zero());
+  let (mut numerator, mut denominator) = (value.numer().clone(), value.denom().clone());
+  loop {
+    let (whole, remainder) = numerator.div_mod_floor(&denominator);
+    let q2 = &q0 + &whole * &q1;
+    if q2 > *max {
+      break;
+    }
+    let p2 = &p0 + &whole * &p1;
+    p0 = mem::replace(&mut p1, p2);
+    q0 = mem::replace(&mut q1, q2);
+    numerator = mem::replace(&mut denominator, remainder);
+  }
+  let steps = (max - &q0).div_floor(&q1);
+  let semiconvergent = BigRational::new(&p0 + &steps * &p1, &q0 + &steps * &q1);
+  let convergent = BigRational::new(p1, q1);
+  if (&convergent - &value).abs() <= (&semiconvergent - &value).abs() { convergent } else { semiconvergent }
+}
+
+// The nearest multiple of the step, halves to even.
+pub(crate) fn round_to(value: BigRational, step: &BigRational) -> BigRational {
+  assert!(!step.is_zero(), "the step must not be zero");
+  BigRational::from_integer(Rounding::HalfEven.round(&(value / step))) * step
+}
+
+// Ten to the power of the exponent.
+fn power_of_ten(exponent: isize) -> BigRational {
+  let power = BigRational::from_integer(pow(BigInt::from(10), exponent.unsigned_abs()));
+  if exponent < 0 { power.recip() } else { power }
+}
+
+// The value rounded to the given number of significant decimal digits, halves to even.
+pub(crate) fn significant_digits(value: BigRational, digits: usize) -> BigRational {
+  assert!(digits > 0, "there must be at least one significant digit");
+  if value.is_zero() {
+    return value;
+  }
+  let magnitude = value.abs();
+  let mut exponent = magnitude.numer().to_string().len() as isize - magnitude.denom().to_string().len() as isize;
+  while power_of_ten(exponent) > magnitude {
+    exponent -= 1;
+  }
+  while power_of_ten(exponent + 1) <= magnitude {
+    exponent += 1;
+  }
+  let scale = power_of_ten(digits as isize - 1 - exponent);
+  BigRational::from_integer(Rounding::HalfEven.round(&(value * &scale))) / scale
+}
+
+/// An error converting a unit into a primitive.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConversionError {
+  /// The value is not a finite number, eg an `f64` holding NaN.
+  NotFinite,
+  /// The value is not a whole number, eg 1.5 into an integer.
+  NotIntegral,
+  /// The value does not fit the type, eg 300 into a `u8`.
+  OutOfRange,
+}
+
+impl fmt::Display for ConversionError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      ConversionError::NotFinite => write!(f, "the value is not a finite number"),
+      ConversionError::NotIntegral => write!(f, "the value is not a whole number"),
+      ConversionError::OutOfRange => write!(f, "the value is out of range for the type"),
+    }
+  }
+}
+
+impl Error for ConversionError {}
+
+// The value as an integer, if it is a whole number that fits.
+#[doc(hidden)]
+pub fn integer<S,I>(value: &S) -> Result<I, ConversionError> where S: Storage, I: TryFrom<BigInt> {
+  let value = value.to_rational().ok_or(ConversionError::NotFinite)?;
+  if !value.is_integer() {
+    return Err(ConversionError::NotIntegral);
+  }
+  I::try_from(value.to_integer()).map_err(|_| ConversionError::OutOfRange)
+}
+
+// The exact value of a float, or an error if it is not a finite number.
+#[doc(hidden)]
+pub fn exact(value: f64) -> Result<BigRational, ConversionError> {
+  BigRational::from_float(value).ok_or(ConversionError::NotFinite)
+}
+
+// The value rounded to an integer, if it fits.
+pub(crate) fn rounded<S,I>(value: &S, rounding: Rounding) -> Result<I, ConversionError> where S: Storage, I: TryFrom<BigInt> {
+  let value = value.to_rational().ok_or(ConversionError::NotFinite)?;
+  I::try_from(rounding.round(&value)).map_err(|_| ConversionError::OutOfRange)
+}
+
+// The nearest `f64`, halves to even. Values beyond its range are infinite.
+pub(crate) fn nearest_f64<S>(value: &S) -> Option<f64> where S: Storage {
+  value.to_rational().and_then(|value| value.to_f64())
+}
+
+// The nearest `f32`, halves to even. Rounding to the nearest `f64` first can land exactly halfway between
+// two `f32`s, so rounding again would round twice, and then the exact value decides between them.
+pub(crate) fn nearest_f32<S>(value: &S) -> Option<f32> where S: Storage {
+  let value = value.to_rational()?;
+  let double = value.to_f64()?;
+  let single = double as f32;
+  if single.is_infinite() || f64::from(single) == double {
+    return Some(single);
+  }
+  // The neighbouring `f32` on the other side of the `f64`.
+  let other = if double.abs() > f64::from(single).abs() {
+    f32::from_bits(single.to_bits() + 1)
+  } else {
+    f32::from_bits(single.to_bits() - 1)
+  };
+  if (f64::from(single) + f64::from(other)) / 2.0 != double {
+    return Some(single);
+  }
+  match value.cmp(&BigRational::from_float(double)?) {
+    Ordering::Equal => Some(single),
+    Ordering::Greater => Some(single.max(other)),
+    Ordering::Less => Some(single.min(other)),
+  }
+}
+
+#[test]
+fn rounds_by_mode() {
+  let value = |numerator: i64| BigRational::new(BigInt::from(numerator), BigInt::from(2));
+  let round = |rounding: Rounding| [-3, -1, 1, 3, 5].iter().map(|&n| rounding.round(&value(n))).collect::<Vec<_>>();
+  let integers = |values: &[i64]| values.iter().cloned().map(BigInt::from).collect::<Vec<_>>();
+  assert_eq!(round(Rounding::Floor), integers(&[-2, -1, 0, 1, 2]));
+  assert_eq!(round(Rounding::Ceil), integers(&[-1, 0, 1, 2, 3]));
+  assert_eq!(round(Rounding::HalfEven), integers(&[-2, 0, 0, 2, 2]));
+  assert_eq!(round(Rounding::TowardZero), integers(&[-1, 0, 0, 1, 2]));
+}
+
+#[test]
+fn converts_to_nearest_float() {
+  assert_eq!(Meter::from(1).to_f64(), Some(1.0));
+  assert_eq!((Meter::from(1) / 10i64).to_f64(), Some(0.1));
+  assert_eq!((Meter::from(1) / 3i64).to_f32(), Some(1.0 / 3.0));
+  assert_eq!(meter::Meter::<f64>::new(f64::NAN).to_f64(), None);
+  // Just off halfway between two `f32`s rounds to the halfway `f64`, where rounding again would go to even.
+  let tiny = BigRational::new(BigInt::one(), BigInt::from(1i64 << 60));
+  let halfway = |numerator: i64| BigRational::new(BigInt::from(numerator), BigInt::from(1i64 << 24));
+  assert_eq!(Meter::from(halfway((1 << 24) + 3) - tiny.clone()).to_f32(), Some(1.0 + f32::EPSILON));
+  assert_eq!(Meter::from(halfway((1 << 24) + 1) + tiny).to_f32(), Some(1.0 + f32::EPSILON));
+  assert_eq!(Meter::from(halfway((1 << 24) + 1)).to_f32(), Some(1.0));
+}
+
+#[test]
+fn converts_to_integers() {
+  assert_eq!(i64::try_from(Meter::from(3)), Ok(3));
+  assert_eq!(u8::try_from(Kilo::<Meter>::from(300)), Err(ConversionError::OutOfRange));
+  assert_eq!(i32::try_from(Meter::from(3) / 2), Err(ConversionError::NotIntegral));
+  assert_eq!(u64::try_from(meter::Meter::<f64>::new(f64::INFINITY)), Err(ConversionError::NotFinite));
+  assert_eq!((Meter::from(7) / 2i64).to_integer::<i16>(Rounding::HalfEven), Ok(4));
+  assert_eq!(Milli::<Meter>::from(Meter::from(1) / 3).round(Rounding::Floor), Milli::<Meter>::from(333));
+}
+
+#[test]
+fn converts_from_floats_and_decimals() {
+  assert_eq!(Meter::try_from(1.5), Ok(Meter::from(3) / 2i64));
+  assert_eq!(Kilo::<Meter>::try_from(0.25f32), Ok(Kilo::<Meter>::from(1) / 4i64));
+  assert_eq!(Meter::try_from(0.1), Ok(Meter::from(BigRational::new(BigInt::from(3_602_879_701_896_397i64), BigInt::from(1i64 << 55)))));
+  assert_eq!(Meter::try_from(f64::NAN), Err(ConversionError::NotFinite));
+  assert_eq!(Meter::try_from(f32::NEG_INFINITY), Err(ConversionError::NotFinite));
+  assert_eq!(Meter::from_decimal("0.1"), Ok(Meter::from(1) / 10i64));
+  assert_eq!(Milli::<Meter>::from_decimal("-1.5e3").map(Meter::from), Ok(Meter::from(-3) / 2i64));
+  assert!(Meter::from_decimal("0.1 m").is_err());
+}
+
+#[test]
+fn approximates_with_bounded_denominators() {
+  let ratio = |n: i64, d: i64| BigRational::new(BigInt::from(n), BigInt::from(d));
+  let pi = ratio(3_141_592_653_589_793, 1_000_000_000_000_000);
+  assert_eq!(approximate(pi.clone(), &BigInt::from(10)), ratio(22, 7));
+  assert_eq!(approximate(pi.clone(), &BigInt::from(1_000)), ratio(355, 113));
+  assert_eq!(approximate(-pi, &BigInt::from(100)), ratio(-311, 99));
+  assert_eq!(approximate(ratio(3, 4), &BigInt::from(4)), ratio(3, 4));
+  assert_eq!(approximate(ratio(1, 3), &BigInt::from(1)), ratio(0, 1));
+  assert_eq!((Meter::from(2) / 3i64).approximate(2), Meter::from(1) / 2i64);
+}
+
+#[test]
+fn rounds_to_steps_and_significant_digits() {
+  let length = Milli::<Meter>::from(12_345) / 1_000i64;
+  assert_eq!(length.clone().round_to(Milli::<Meter>::from(5)), Milli::<Meter>::from(10));
+  assert_eq!(length.clone().round_to(Milli::<Meter>::from(1) / 4i64), Milli::<Meter>::from(49) / 4i64);
+  assert_eq!(length.clone().limit_significant_digits(3), Milli::<Meter>::from(123) / 10i64);
+  assert_eq!((-length).limit_significant_digits(1), Milli::<Meter>::from(-10));
+  assert_eq!(Meter::from(99_951).limit_significant_digits(3), Meter::from(100_000));
+  assert_eq!((Meter::from(1) / 3_000i64).limit_significant_digits(2), Meter::from(33) / 100_000i64);
+  assert_eq!(Meter::from(0).limit_significant_digits(2), Meter::from(0));
+}
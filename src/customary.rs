@@ -0,0 +1,214 @@
+//! Imperial and US customary units, defined exactly in SI units, eg an inch is 127/5000 m.
+//!
+//! Lengths and masses convert to their SI base unit with `base()`, so they mix with prefixed units, eg
+//! `Kilo::<Meter>::from(1) + Mile::from(1)`. Every unit converts exactly to its quantity, so arithmetic
+//! across units, eg `Foot::from(1).quantity() + Inch::from(1).quantity()`, is lossless.
+use base::{gram, meter};
+use prefix::Kilo;
+#[cfg(test)] use base::*;
+#[cfg(test)] use derived::Newton;
+#[cfg(test)] use prefix::*;
+#[cfg(test)] use quantity::{IntoQuantity, Volume};
+#[cfg(test)] use accepted::Litre;
+#[cfg(test)] use IntoBase;
+
+registered_units! {
+  //
+  // Length
+  //
+
+  generate_derived! {
+    name      = Inch,
+    module    = inch,
+    longform  = "inch",
+    shortform = "in",
+    dimension = Length,
+    factor    = (127, 5_000),
+    doc       = "An inch is a unit measuring distance, equal to 0.0254 m.",
+  }
+
+  generate_derived! {
+    name      = Foot,
+    module    = foot,
+    longform  = "foot",
+    shortform = "ft",
+    dimension = Length,
+    factor    = (381, 1_250),
+    doc       = "A foot is a unit measuring distance, equal to 12 in or 0.3048 m.",
+  }
+
+  generate_derived! {
+    name      = Yard,
+    module    = yard,
+    longform  = "yard",
+    shortform = "yd",
+    dimension = Length,
+    factor    = (1_143, 1_250),
+    doc       = "A yard is a unit measuring distance, equal to 3 ft or 0.9144 m.",
+  }
+
+  generate_derived! {
+    name      = Mile,
+    module    = mile,
+    longform  = "mile",
+    shortform = "mi",
+    dimension = Length,
+    factor    = (201_168, 125),
+    doc       = "A mile is a unit measuring distance, equal to 1760 yd or 1609.344 m.",
+  }
+
+  //
+  // Mass
+  //
+
+  generate_derived! {
+    name      = Ounce,
+    module    = ounce,
+    longform  = "ounce",
+    shortform = "oz",
+    dimension = Mass,
+    factor    = (45_359_237, 1_600_000_000i64),
+    doc       = "An ounce is a unit measuring weight, equal to 1/16 lb or 28.349523125 g.",
+  }
+
+  generate_derived! {
+    name      = Pound,
+    module    = pound,
+    longform  = "pound",
+    shortform = "lb",
+    dimension = Mass,
+    factor    = (45_359_237, 100_000_000),
+    doc       = "A pound is a unit measuring weight, equal to 0.45359237 kg.",
+  }
+
+  generate_derived! {
+    name      = Stone,
+    module    = stone,
+    longform  = "stone",
+    shortform = "st",
+    dimension = Mass,
+    factor    = (635_029_318, 100_000_000),
+    doc       = "A stone is a unit measuring weight, equal to 14 lb or 6.35029318 kg.",
+  }
+
+  //
+  // Area
+  //
+
+  generate_derived! {
+    name      = SquareFoot,
+    module    = square_foot,
+    longform  = "square foot",
+    shortform = "ft²",
+    dimension = Area,
+    factor    = (9_290_304, 100_000_000),
+    doc       = "A square foot is a unit measuring area, equal to 0.09290304 m².",
+  }
+
+  generate_derived! {
+    name      = Acre,
+    module    = acre,
+    longform  = "acre",
+    shortform = "ac",
+    dimension = Area,
+    factor    = (40_468_564_224i64, 10_000_000),
+    doc       = "An acre is a unit measuring area, equal to 43560 ft² or 4046.8564224 m².",
+  }
+
+  //
+  // Volume
+  //
+
+  generate_derived! {
+    name      = UsGallon,
+    module    = us_gallon,
+    longform  = "US gallon",
+    shortform = "US gal",
+    dimension = Volume,
+    factor    = (3_785_411_784i64, 1_000_000_000_000i64),
+    doc       = "A US gallon is a unit measuring volume, equal to 231 in³ or 3.785411784 L.",
+  }
+
+  generate_derived! {
+    name      = ImperialGallon,
+    module    = imperial_gallon,
+    longform  = "imperial gallon",
+    shortform = "imp gal",
+    dimension = Volume,
+    factor    = (454_609, 100_000_000),
+    doc       = "An imperial gallon is a unit measuring volume, equal to 4.54609 L.",
+  }
+
+  generate_derived! {
+    name      = UsFluidOunce,
+    module    = us_fluid_ounce,
+    longform  = "US fluid ounce",
+    shortform = "US fl oz",
+    dimension = Volume,
+    factor    = (3_785_411_784i64, 128_000_000_000_000i64),
+    doc       = "A US fluid ounce is a unit measuring volume, equal to 1/128 US gal.",
+  }
+
+  generate_derived! {
+    name      = ImperialFluidOunce,
+    module    = imperial_fluid_ounce,
+    longform  = "imperial fluid ounce",
+    shortform = "imp fl oz",
+    dimension = Volume,
+    factor    = (454_609, 16_000_000_000i64),
+    doc       = "An imperial fluid ounce is a unit measuring volume, equal to 1/160 imp gal.",
+  }
+
+  //
+  // Force
+  //
+
+  generate_derived! {
+    name      = PoundForce,
+    module    = pound_force,
+    longform  = "pound-force",
+    shortform = "lbf",
+    dimension = Force,
+    factor    = (44_482_216_152_605i64, 10_000_000_000_000i64),
+    doc       = "A pound-force is a unit measuring force, the weight of a pound under standard gravity, equal to 4.4482216152605 N.",
+  }
+}
+
+into_base! {
+  inch::Inch => meter::Meter<S>,
+  foot::Foot => meter::Meter<S>,
+  yard::Yard => meter::Meter<S>,
+  mile::Mile => meter::Meter<S>,
+  ounce::Ounce => Kilo<gram::Gram<S>>,
+  pound::Pound => Kilo<gram::Gram<S>>,
+  stone::Stone => Kilo<gram::Gram<S>>,
+}
+
+#[test]
+fn converts_exactly_to_the_base() {
+  assert_eq!(IntoBase::<Meter>::base(Inch::from(1)), Milli::<Meter>::from(254) / 10);
+  assert_eq!(IntoBase::<Meter>::base(Mile::from(1)), Kilo::<Meter>::from(1_609_344) / 1_000_000);
+  assert_eq!(IntoBase::<Kilogram>::base(Stone::from(1)), IntoBase::<Kilogram>::base(Pound::from(14)));
+  assert_eq!(Litre::from(UsGallon::from(1).quantity()), Milli::<Litre>::from(3_785_411_784i64) / 1_000_000);
+  assert_eq!(Newton::from(PoundForce::from(1).quantity()), Newton::from(Pound::from(1) * Meter::from(980_665) / (Second::from(1) * Second::from(1))) / 100_000);
+}
+
+#[test]
+fn mixed_arithmetic_is_lossless() {
+  assert_eq!(Foot::from(1).quantity() + Inch::from(1).quantity(), Inch::from(13).quantity());
+  assert_eq!(Yard::from(1_760).quantity(), Mile::from(1).quantity());
+  assert_eq!(Ounce::from(16).quantity(), Pound::from(1).quantity());
+  assert_eq!(Acre::from(1).quantity(), SquareFoot::from(43_560).quantity());
+  assert_eq!(UsFluidOunce::from(128).quantity(), UsGallon::from(1).quantity());
+  assert_eq!(ImperialFluidOunce::from(160).quantity(), ImperialGallon::from(1).quantity());
+  assert_eq!(Kilo::<Meter>::from(1) + Mile::from(1), Kilo::<Meter>::from(2_609_344) / 1_000_000);
+  assert_eq!(UsGallon::from(1).quantity(), Volume::from(Inch::from(1) * Inch::from(1) * Inch::from(1)) * 231);
+}
+
+#[test]
+fn has_plural_forms() {
+  assert_eq!(format!("{:#}", Foot::from(6)), "6 feet");
+  assert_eq!(format!("{:#}", Inch::from(2)), "2 inches");
+  assert_eq!(format!("{:#}", PoundForce::from(2)), "2 pounds-force");
+  assert_eq!("2 US gal".parse::<UsGallon>(), Ok(UsGallon::from(2)));
+}
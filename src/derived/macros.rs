@@ -0,0 +1,584 @@
+macro_rules! derived_from_primitives {
+  {
+    $unit:ident,
+    [$($primitive:ty,)*]
+  } => {
+    $(
+      impl<S> From<$primitive> for $unit<S> where S: Storage {
+        fn from(value: $primitive) -> Self {
+          Self::new(::storage::from_integer(value))
+        }
+      }
+    )*
+  }
+}
+
+macro_rules! derived_from_floats {
+  {
+    $unit:ident,
+    [$($float:ty,)*]
+  } => {
+    $(
+      impl<S> ::std::convert::TryFrom<$float> for $unit<S> where S: Storage {
+        type Error = ::convert::ConversionError;
+        fn try_from(value: $float) -> Result<Self, Self::Error> {
+          ::convert::exact(value.into()).map(Self::from)
+        }
+      }
+    )*
+  }
+}
+
+macro_rules! derived_into_integers {
+  {
+    $unit:ident,
+    [$($primitive:ty,)*]
+  } => {
+    $(
+      impl<S> ::std::convert::TryFrom<$unit<S>> for $primitive where S: Storage {
+        type Error = ::convert::ConversionError;
+        fn try_from(unit: $unit<S>) -> Result<Self, Self::Error> {
+          ::convert::integer(&unit.value)
+        }
+      }
+    )*
+  }
+}
+
+macro_rules! derived_div_and_mul_with_primitives {
+  {
+    $unit:ident,
+    [$($primitive:ty,)*]
+  } => {
+    $(
+      impl<S> Div<$primitive> for $unit<S> where S: Storage {
+        type Output = Self;
+        fn div(self, value: $primitive) -> Self {
+          Self::new(self.value / ::storage::from_integer(value))
+        }
+      }
+
+      impl<S> Mul<$primitive> for $unit<S> where S: Storage {
+        type Output = Self;
+        fn mul(self, value: $primitive) -> Self {
+          Self::new(self.value * ::storage::from_integer(value))
+        }
+      }
+    )*
+  }
+}
+
+// Scalars in the storage of a unit multiply and divide its value directly.
+macro_rules! derived_div_and_mul_with_storage {
+  {
+    $unit:ident,
+    [$($storage:ty,)*]
+  } => {
+    $(
+      impl Div<$storage> for $unit<$storage> {
+        type Output = Self;
+        fn div(self, value: $storage) -> Self {
+          Self::new(self.value / value)
+        }
+      }
+
+      impl Mul<$storage> for $unit<$storage> {
+        type Output = Self;
+        fn mul(self, value: $storage) -> Self {
+          Self::new(self.value * value)
+        }
+      }
+    )*
+  }
+}
+
+// Let derived units take prefixes, eg `Kilo<Newton>`, with the same conversions, arithmetic and comparisons
+// with their prefixes as base units have.
+macro_rules! prefixable {
+  {
+    $($module:ident::$name:ident,)*
+  } => {
+    $(
+      impl<S> ::prefix::Prefixable for $module::$name<S> where S: ::storage::Storage {}
+
+      impl<S> ::IntoBase<$module::$name<S>> for $module::$name<S> where S: ::storage::Storage {
+        fn base(self) -> Self {
+          self
+        }
+      }
+
+      impl<S,P> From<P> for $module::$name<S> where S: ::storage::Storage, P: ::prefix::Prefix<$module::$name<S>> {
+        fn from(value: P) -> Self {
+          value.base()
+        }
+      }
+
+      // Prefixes below one measure both values in the prefix, as for base units.
+      impl<S,P> ::std::ops::Add<P> for $module::$name<S> where S: ::storage::Storage, P: ::prefix::Prefix<$module::$name<S>> {
+        type Output = Self;
+        fn add(self, other: P) -> Self {
+          if ::prefix::is_fraction::<Self,P>() { (P::scale(self) + other).base() } else { self + other.base() }
+        }
+      }
+
+      impl<S,P> ::std::ops::Sub<P> for $module::$name<S> where S: ::storage::Storage, P: ::prefix::Prefix<$module::$name<S>> {
+        type Output = Self;
+        fn sub(self, other: P) -> Self {
+          if ::prefix::is_fraction::<Self,P>() { (P::scale(self) - other).base() } else { self - other.base() }
+        }
+      }
+
+      impl<S,P> PartialEq<P> for $module::$name<S> where S: ::storage::Storage, P: ::prefix::Prefix<$module::$name<S>> {
+        fn eq(&self, other: &P) -> bool {
+          if ::prefix::is_fraction::<Self,P>() {
+            ::Unit::value_ref(&P::scale(self.clone())) == ::Unit::value_ref(other)
+          } else {
+            ::Unit::value_ref(self) == ::Unit::value_ref(&other.clone().base())
+          }
+        }
+      }
+
+      impl<S,P> PartialOrd<P> for $module::$name<S> where S: ::storage::Storage, P: ::prefix::Prefix<$module::$name<S>> {
+        fn partial_cmp(&self, other: &P) -> Option<::std::cmp::Ordering> {
+          if ::prefix::is_fraction::<Self,P>() {
+            ::Unit::value_ref(&P::scale(self.clone())).partial_cmp(::Unit::value_ref(other))
+          } else {
+            ::Unit::value_ref(self).partial_cmp(::Unit::value_ref(&other.clone().base()))
+          }
+        }
+      }
+    )*
+  }
+}
+
+// A quantity converts into a unit of its dimension, except into a unit of another kind, such as a temperature
+// difference. Those convert from a quantity only within the crate.
+macro_rules! derived_from_quantity {
+  {
+    $name:ident,
+    $dimension:ident,
+    Plain
+  } => {
+    impl<S> From<QuantityOf<S>> for $name<S> where S: Storage {
+      fn from(val: QuantityOf<S>) -> Self {
+        Self::new(val.value().scale_by(&INVERSE_FACTOR))
+      }
+    }
+
+    #[cfg(test)]
+    quickcheck! {
+      fn into_quantity_and_back_is_equal(value: $name) -> bool {
+        let quantity = ::quantity::$dimension::from(value.clone());
+        <$name>::from(quantity).value() == value.value()
+      }
+    }
+  };
+  {
+    $name:ident,
+    $dimension:ident,
+    $kind:ident
+  } => {
+    impl<S> $name<S> where S: Storage {
+      pub(crate) fn from_quantity(val: QuantityOf<S>) -> Self {
+        Self::new(val.value().scale_by(&INVERSE_FACTOR))
+      }
+    }
+
+    #[cfg(test)]
+    quickcheck! {
+      fn into_quantity_and_back_is_equal(value: $name) -> bool {
+        let quantity = ::quantity::$dimension::from(value.clone());
+        <$name>::from_quantity(quantity).value() == value.value()
+      }
+    }
+  }
+}
+
+macro_rules! generate_derived {
+  {
+    name      = $name:ident,
+    module    = $module:ident,
+    longform  = $longform:expr,
+    shortform = $shortform:expr,
+    dimension = $dimension:ident,
+    $doc:meta,
+  } => (
+    generate_derived! {
+      name      = $name,
+      module    = $module,
+      longform  = $longform,
+      shortform = $shortform,
+      dimension = $dimension,
+      factor    = (1, 1),
+      $doc,
+    }
+  );
+  {
+    name      = $name:ident,
+    module    = $module:ident,
+    longform  = $longform:expr,
+    shortform = $shortform:expr,
+    dimension = $dimension:ident,
+    factor    = ($numerator:expr, $denominator:expr),
+    $doc:meta,
+  } => (
+    generate_derived! {
+      name      = $name,
+      module    = $module,
+      longform  = $longform,
+      shortform = $shortform,
+      dimension = $dimension,
+      factor    = ($numerator, $denominator),
+      kind      = Plain,
+      $doc,
+    }
+  );
+  {
+    name      = $name:ident,
+    module    = $module:ident,
+    longform  = $longform:expr,
+    shortform = $shortform:expr,
+    dimension = $dimension:ident,
+    kind      = $kind:ident,
+    $doc:meta,
+  } => (
+    generate_derived! {
+      name      = $name,
+      module    = $module,
+      longform  = $longform,
+      shortform = $shortform,
+      dimension = $dimension,
+      factor    = (1, 1),
+      kind      = $kind,
+      $doc,
+    }
+  );
+  {
+    name      = $name:ident,
+    module    = $module:ident,
+    longform  = $longform:expr,
+    shortform = $shortform:expr,
+    dimension = $dimension:ident,
+    factor    = ($numerator:expr, $denominator:expr),
+    kind      = $kind:ident,
+    $doc:meta,
+  } => (
+    #[doc = concat!("The ", $longform, ", generic over its numeric storage.")]
+    pub mod $module {
+      #[cfg(test)] use quickcheck::{Arbitrary, Gen};
+      use Unit;
+      use derived::Derived;
+      use num::bigint::BigInt;
+      use num::rational::BigRational;
+      use quantity::{Quantity, IntoQuantity, QuantityKind};
+      use format;
+      use parse::{self, ParseError};
+      use std::cmp::Ordering;
+      use std::fmt;
+      use std::ops::*;
+      use std::str::FromStr;
+      use storage::{Factor, Storage};
+
+      lazy_static! {
+        static ref SHORTFORM: &'static str = $shortform;
+        static ref LONGFORM: &'static str = $longform;
+        // The size of the unit in coherent units, eg 5/9 for a Fahrenheit difference.
+        static ref FACTOR: Factor = Factor::new(BigRational::new(BigInt::from($numerator), BigInt::from($denominator)));
+        static ref INVERSE_FACTOR: Factor = FACTOR.recip();
+      }
+
+      // The quantity of the same dimension and storage.
+      type QuantityOf<S> = Quantity<<::quantity::$dimension as IntoQuantity>::Dimension, S>;
+
+      #[$doc]
+      #[derive(Clone, Debug)]
+      pub struct $name<S = BigRational> where S: Storage {
+        value: S,
+      }
+
+      impl<S> Unit for $name<S> where S: Storage {
+        type Storage = S;
+        fn new(value: S) -> Self {
+          $name {
+            value
+          }
+        }
+        fn shortform() -> String {
+          (*SHORTFORM).into()
+        }
+        fn longform() -> String {
+          (*LONGFORM).into()
+        }
+        fn value(self) -> S {
+          self.value
+        }
+        fn value_ref(&self) -> &S {
+          &self.value
+        }
+      }
+
+      #[cfg(test)]
+      impl Arbitrary for $name {
+        fn arbitrary<G: Gen>(g: &mut G) -> Self {
+          let (numerator, denominator) = (g.gen::<i64>(), g.gen::<i64>());
+          let denominator = if denominator == 0 { 1 } else { denominator }; // The denominator cannot be zero.
+
+          let (numerator, denominator) = (BigInt::from(numerator), BigInt::from(denominator));
+          let rational = BigRational::new(numerator, denominator);
+          Self::new(rational)
+        }
+      }
+
+      #[test]
+      fn has_right_shortform() {
+        assert_eq!(<$name>::shortform(), $shortform)
+      }
+
+      #[test]
+      fn has_right_longform() {
+        assert_eq!(<$name>::longform(), $longform)
+      }
+
+      //
+      // Markers
+      //
+      impl<S> Derived for $name<S> where S: Storage {}
+
+      impl<S> IntoQuantity for $name<S> where S: Storage {
+        type Dimension = <::quantity::$dimension as IntoQuantity>::Dimension;
+        fn quantity(self) -> QuantityOf<S> {
+          Quantity::new(self.value.scale_by(&FACTOR))
+        }
+        fn quantity_kind() -> QuantityKind {
+          QuantityKind::$kind
+        }
+      }
+
+      //
+      // Conversions
+      //
+      impl<S> From<BigInt> for $name<S> where S: Storage {
+        fn from(val: BigInt) -> Self {
+          let fraction = BigRational::from_integer(val);
+          Self::from(fraction)
+        }
+      }
+
+      impl<S> From<BigRational> for $name<S> where S: Storage {
+        fn from(val: BigRational) -> Self {
+          Self::new(S::from_rational(&val))
+        }
+      }
+
+      // Convert from the product of base units.
+      derived_from_quantity! { $name, $dimension, $kind }
+
+      // Convert into the product of base units.
+      impl<S> From<$name<S>> for QuantityOf<S> where S: Storage {
+        fn from(val: $name<S>) -> Self {
+          val.quantity()
+        }
+      }
+
+      derived_from_primitives! { $name, [i64, u64, i32, u32, i16, u16, i8, u8, isize, usize,] }
+      derived_from_floats! { $name, [f64, f32,] }
+      derived_into_integers! { $name, [i64, u64, i32, u32, i16, u16, i8, u8, i128, u128, isize, usize,] }
+
+      //
+      // Operations on self
+      //
+      impl<S> Add for $name<S> where S: Storage {
+        type Output = Self;
+        fn add(self, other: Self) -> Self {
+          Self::new(self.value + other.value())
+        }
+      }
+
+      #[cfg(test)]
+      quickcheck! {
+        fn can_add_self(first: $name, second: $name) -> bool {
+          let check = first.clone().value() + second.clone().value();
+          (first + second).value() == check
+        }
+      }
+
+      impl<S> Sub for $name<S> where S: Storage {
+        type Output = Self;
+        fn sub(self, other: Self) -> Self {
+          Self::new(self.value - other.value())
+        }
+      }
+
+      #[cfg(test)]
+      quickcheck! {
+        fn can_sub_self(first: $name, second: $name) -> bool {
+          let check = first.clone().value() - second.clone().value();
+          (first - second).value() == check
+        }
+      }
+
+      //
+      // Operations on any dimension
+      //
+      impl<S,U> Div<U> for $name<S> where S: Storage, U: IntoQuantity, QuantityOf<S>: Div<U> {
+        type Output = <QuantityOf<S> as Div<U>>::Output;
+        fn div(self, other: U) -> Self::Output {
+          self.quantity() / other
+        }
+      }
+
+      impl<S,U> Mul<U> for $name<S> where S: Storage, U: IntoQuantity, QuantityOf<S>: Mul<U> {
+        type Output = <QuantityOf<S> as Mul<U>>::Output;
+        fn mul(self, other: U) -> Self::Output {
+          self.quantity() * other
+        }
+      }
+
+      #[cfg(test)]
+      quickcheck! {
+        fn can_mul_and_div_any(first: $name, second: ::base::Second) -> bool {
+          let second = if second.value_ref() == &BigRational::from_integer(0.into()) { ::base::Second::from(1) } else { second };
+          let check = first.clone().quantity();
+          (first * second.clone() / second) == check
+        }
+      }
+
+      //
+      // Dividing and multiplication are defined on integral types.
+      //
+      impl<S> Div<BigRational> for $name<S> where S: Storage {
+        type Output = Self;
+        fn div(self, value: BigRational) -> Self {
+          Self::new(self.value.scale(&value.recip()))
+        }
+      }
+
+      impl<S> Div<BigInt> for $name<S> where S: Storage {
+        type Output = Self;
+        fn div(self, value: BigInt) -> Self {
+          self / BigRational::from_integer(value)
+        }
+      }
+
+      impl<S> Mul<BigRational> for $name<S> where S: Storage {
+        type Output = Self;
+        fn mul(self, value: BigRational) -> Self {
+          Self::new(self.value.scale(&value))
+        }
+      }
+
+      impl<S> Mul<BigInt> for $name<S> where S: Storage {
+        type Output = Self;
+        fn mul(self, value: BigInt) -> Self {
+          self * BigRational::from_integer(value)
+        }
+      }
+
+      derived_div_and_mul_with_primitives! { $name, [i64, u64, i32, u32, i16, u16, i8, u8, isize, usize,] }
+      derived_div_and_mul_with_storage! { $name, [f64, f32, ::num::rational::Ratio<i64>,] }
+
+      //
+      // Formatting
+      //
+      impl<S> fmt::Display for $name<S> where S: Storage {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+          format::display(self, f)
+        }
+      }
+
+      impl<S> fmt::LowerExp for $name<S> where S: Storage {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+          format::scientific(self, f)
+        }
+      }
+
+      impl<S> fmt::UpperExp for $name<S> where S: Storage {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+          format::upper_scientific(self, f)
+        }
+      }
+
+      #[test]
+      fn can_display() {
+        let value = <$name>::from(3) / 2;
+        assert_eq!(format!("{}", value), format!("1.5 {}", <$name>::shortform()));
+        assert_eq!(format!("{:#}", value), format!("1.5 {}", format::plural(&<$name>::longform())));
+        assert_eq!(format!("{:.3e}", value * 1000), format!("1.500e3 {}", <$name>::shortform()));
+      }
+
+      //
+      // Serialization
+      //
+      #[cfg(feature = "serde")]
+      impl<S> ::serde::Serialize for $name<S> where S: Storage {
+        fn serialize<Z>(&self, serializer: Z) -> Result<Z::Ok, Z::Error> where Z: ::serde::Serializer {
+          ::serialization::exact::serialize(self, serializer)
+        }
+      }
+
+      #[cfg(feature = "serde")]
+      impl<'de, S> ::serde::Deserialize<'de> for $name<S> where S: Storage {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: ::serde::Deserializer<'de> {
+          ::serialization::exact::deserialize(deserializer)
+        }
+      }
+
+      impl<S> FromStr for $name<S> where S: Storage {
+        type Err = ParseError;
+        fn from_str(input: &str) -> Result<Self, ParseError> {
+          parse::parse_exact::<Self>(input)
+        }
+      }
+
+      #[cfg(test)]
+      quickcheck! {
+        fn can_parse_display(value: $name) -> bool {
+          value.to_string().parse::<$name>() == Ok(value)
+        }
+      }
+
+      //
+      // Equals
+      //
+      impl<S> PartialEq for $name<S> where S: Storage {
+        fn eq(&self, other: &Self) -> bool {
+          self.value_ref() == other.value_ref()
+        }
+      }
+
+      impl<S> Eq for $name<S> where S: Storage + Eq {}
+
+      #[cfg(test)]
+      quickcheck! {
+        fn can_eq_self(value: $name) -> bool {
+          let duplicate = value.clone();
+          value == duplicate
+        }
+      }
+
+      //
+      // Ordering
+      //
+      impl<S> PartialOrd for $name<S> where S: Storage {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+          self.value_ref().partial_cmp(other.value_ref())
+        }
+      }
+
+      impl<S> Ord for $name<S> where S: Storage + Ord {
+        fn cmp(&self, other: &Self) -> Ordering {
+          self.value_ref().cmp(other.value_ref())
+        }
+      }
+
+      #[cfg(test)]
+      quickcheck! {
+        fn can_order_self(first: $name, second: $name) -> bool {
+          first.cmp(&second) == first.value_ref().cmp(second.value_ref())
+        }
+      }
+    }
+    #[$doc]
+    pub type $name = self::$module::$name<::num::rational::BigRational>;
+  )
+}
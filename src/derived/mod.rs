@@ -0,0 +1,257 @@
+use quantity::IntoQuantity;
+#[cfg(test)] use base::*;
+#[cfg(test)] use prefix::*;
+#[cfg(test)] use quantity::{Activity, Energy, EquivalentDose, Force, Frequency};
+#[cfg(test)] use IntoBase;
+
+#[macro_use] mod macros;
+
+/// A unit derived from the SI base units, such as the newton.
+pub trait Derived: IntoQuantity {}
+
+registered_units! {
+  generate_derived! {
+    name      = Radian,
+    module    = radian,
+    longform  = "radian",
+    shortform = "rad",
+    dimension = Angle,
+    doc       = "A radian is a unit measuring plane angle, equal to m·m⁻¹.",
+  }
+
+  generate_derived! {
+    name      = Steradian,
+    module    = steradian,
+    longform  = "steradian",
+    shortform = "sr",
+    dimension = SolidAngle,
+    doc       = "A steradian is a unit measuring solid angle, equal to m²·m⁻².",
+  }
+
+  generate_derived! {
+    name      = Hertz,
+    module    = hertz,
+    longform  = "hertz",
+    shortform = "Hz",
+    dimension = Frequency,
+    doc       = "A hertz is a unit measuring frequency, equal to s⁻¹.",
+  }
+
+  generate_derived! {
+    name      = Newton,
+    module    = newton,
+    longform  = "newton",
+    shortform = "N",
+    dimension = Force,
+    doc       = "A newton is a unit measuring force, equal to kg·m·s⁻².",
+  }
+
+  generate_derived! {
+    name      = Pascal,
+    module    = pascal,
+    longform  = "pascal",
+    shortform = "Pa",
+    dimension = Pressure,
+    doc       = "A pascal is a unit measuring pressure, equal to kg·m⁻¹·s⁻².",
+  }
+
+  generate_derived! {
+    name      = Joule,
+    module    = joule,
+    longform  = "joule",
+    shortform = "J",
+    dimension = Energy,
+    doc       = "A joule is a unit measuring energy, equal to kg·m²·s⁻².",
+  }
+
+  generate_derived! {
+    name      = Watt,
+    module    = watt,
+    longform  = "watt",
+    shortform = "W",
+    dimension = Power,
+    doc       = "A watt is a unit measuring power, equal to kg·m²·s⁻³.",
+  }
+
+  generate_derived! {
+    name      = Coulomb,
+    module    = coulomb,
+    longform  = "coulomb",
+    shortform = "C",
+    dimension = Charge,
+    doc       = "A coulomb is a unit measuring electric charge, equal to A·s.",
+  }
+
+  generate_derived! {
+    name      = Volt,
+    module    = volt,
+    longform  = "volt",
+    shortform = "V",
+    dimension = Voltage,
+    doc       = "A volt is a unit measuring electric potential difference, equal to kg·m²·s⁻³·A⁻¹.",
+  }
+
+  generate_derived! {
+    name      = Farad,
+    module    = farad,
+    longform  = "farad",
+    shortform = "F",
+    dimension = Capacitance,
+    doc       = "A farad is a unit measuring capacitance, equal to kg⁻¹·m⁻²·s⁴·A².",
+  }
+
+  generate_derived! {
+    name      = Ohm,
+    module    = ohm,
+    longform  = "ohm",
+    shortform = "Ω",
+    dimension = Resistance,
+    doc       = "An ohm is a unit measuring electrical resistance, equal to kg·m²·s⁻³·A⁻².",
+  }
+
+  generate_derived! {
+    name      = Siemens,
+    module    = siemens,
+    longform  = "siemens",
+    shortform = "S",
+    dimension = Conductance,
+    doc       = "A siemens is a unit measuring electrical conductance, equal to kg⁻¹·m⁻²·s³·A².",
+  }
+
+  generate_derived! {
+    name      = Weber,
+    module    = weber,
+    longform  = "weber",
+    shortform = "Wb",
+    dimension = MagneticFlux,
+    doc       = "A weber is a unit measuring magnetic flux, equal to kg·m²·s⁻²·A⁻¹.",
+  }
+
+  generate_derived! {
+    name      = Tesla,
+    module    = tesla,
+    longform  = "tesla",
+    shortform = "T",
+    dimension = MagneticFluxDensity,
+    doc       = "A tesla is a unit measuring magnetic flux density, equal to kg·s⁻²·A⁻¹.",
+  }
+
+  generate_derived! {
+    name      = Henry,
+    module    = henry,
+    longform  = "henry",
+    shortform = "H",
+    dimension = Inductance,
+    doc       = "A henry is a unit measuring inductance, equal to kg·m²·s⁻²·A⁻².",
+  }
+
+  generate_derived! {
+    name      = Lumen,
+    module    = lumen,
+    longform  = "lumen",
+    shortform = "lm",
+    dimension = LuminousFlux,
+    doc       = "A lumen is a unit measuring luminous flux, equal to cd·sr.",
+  }
+
+  generate_derived! {
+    name      = Lux,
+    module    = lux,
+    longform  = "lux",
+    shortform = "lx",
+    dimension = Illuminance,
+    doc       = "A lux is a unit measuring illuminance, equal to cd·sr·m⁻².",
+  }
+
+  generate_derived! {
+    name      = Becquerel,
+    module    = becquerel,
+    longform  = "becquerel",
+    shortform = "Bq",
+    dimension = Activity,
+    doc       = "A becquerel is a unit measuring radioactivity, equal to s⁻¹. It has the quantity of the hertz, so converts into it.",
+  }
+
+  generate_derived! {
+    name      = Gray,
+    module    = gray,
+    longform  = "gray",
+    shortform = "Gy",
+    dimension = AbsorbedDose,
+    doc       = "A gray is a unit measuring absorbed dose, equal to m²·s⁻².",
+  }
+
+  generate_derived! {
+    name      = Sievert,
+    module    = sievert,
+    longform  = "sievert",
+    shortform = "Sv",
+    dimension = EquivalentDose,
+    doc       = "A sievert is a unit measuring equivalent dose, equal to m²·s⁻². It has the quantity of the gray, so converts into it.",
+  }
+
+  generate_derived! {
+    name      = Katal,
+    module    = katal,
+    longform  = "katal",
+    shortform = "kat",
+    dimension = CatalyticActivity,
+    doc       = "A katal is a unit measuring catalytic activity, equal to mol·s⁻¹.",
+  }
+
+  // Every SI derived unit takes prefixes, as the base units do.
+  prefixable! {
+    radian::Radian,
+    steradian::Steradian,
+    hertz::Hertz,
+    newton::Newton,
+    pascal::Pascal,
+    joule::Joule,
+    watt::Watt,
+    coulomb::Coulomb,
+    volt::Volt,
+    farad::Farad,
+    ohm::Ohm,
+    siemens::Siemens,
+    weber::Weber,
+    tesla::Tesla,
+    henry::Henry,
+    lumen::Lumen,
+    lux::Lux,
+    becquerel::Becquerel,
+    gray::Gray,
+    sievert::Sievert,
+    katal::Katal,
+  }
+}
+
+#[test]
+fn takes_prefixes() {
+  assert_eq!(Kilo::<Newton>::from(3) / 2, Newton::from(1_500));
+  assert_eq!(Newton::from(2) + Kilo::<Newton>::from(1), Newton::from(1_002));
+  assert_eq!(Kilo::<Newton>::from(1) + Newton::from(500), Milli::<Newton>::from(1_500_000));
+  assert_eq!(IntoBase::<Watt>::base(Mega::<Watt>::from(2)), Watt::from(2_000_000));
+  assert!(Milli::<Ohm>::from(999) < Ohm::from(1));
+  assert_eq!(format!("{}", Mega::<Watt>::from(5)), "5 MW");
+  assert_eq!(format!("{:#}", Kilo::<Newton>::from(2)), "2 kilonewtons");
+  assert_eq!("3 kN".parse::<Kilo<Newton>>(), Ok(Kilo::<Newton>::from(3)));
+  assert_eq!("1500 N".parse::<Kilo<Newton>>(), Ok(Kilo::<Newton>::from(3) / 2));
+}
+
+#[test]
+fn prefixes_compose_with_the_quantity() {
+  assert_eq!(Kilo::<Newton>::from(2).quantity(), Force::from(2_000));
+  assert_eq!(Mega::<Joule>::from(1).quantity(), Energy::from(Kilo::<Watt>::from(1) * Second::from(1_000)));
+  assert_eq!(Kilo::<Newton>::from(1) * Meter::from(3), Kilo::<Joule>::from(3).quantity());
+}
+
+// The SI tells the hertz from the becquerel, and the gray from the sievert, by what they measure rather than by
+// dimension. They share a quantity, so convert into each other through it.
+#[test]
+fn converts_between_units_of_the_same_quantity() {
+  assert_eq!(Becquerel::from(Hertz::from(50).quantity()), Becquerel::from(50));
+  assert_eq!(Hertz::from(Kilo::<Becquerel>::from(2).quantity()), Hertz::from(2_000));
+  assert_eq!(Sievert::from(Gray::from(3).quantity()), Sievert::from(3));
+  assert_eq!(Activity::from(Becquerel::from(1)), Frequency::from(Hertz::from(1)));
+  assert_eq!(EquivalentDose::from(Sievert::from(1)), Gray::from(1).quantity());
+}
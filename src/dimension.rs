@@ -1,3 +1,6 @@
+use std::marker::PhantomData;
+use std::ops::{Add, Sub, Mul, Div};
+use typenum::{Integer, Sum, Diff};
 use Unit;
 
 pub trait Length: Unit {}
@@ -13,3 +16,52 @@ pub trait Temperature: Unit {}
 pub trait Amount: Unit {}
 
 pub trait Intensity: Unit {}
+
+/// Information, such as bits and bytes. It is dimensionless in the SI, but kept apart from other units.
+pub trait Information: Unit {}
+
+/// A physical dimension, expressed as exponents of the seven SI base dimensions.
+pub trait Dimension {
+  /// The exponents of length, mass, time, current, temperature, amount and intensity, in that order.
+  fn exponents() -> [isize; 7];
+}
+
+/// A dimension checked at compile time. Each parameter is a `typenum` integer holding the exponent of
+/// length, mass, time, current, temperature, amount and intensity respectively.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Dim<L, M, T, I, Th, N, J> {
+  exponents: PhantomData<(L, M, T, I, Th, N, J)>,
+}
+
+impl<L, M, T, I, Th, N, J> Dim<L, M, T, I, Th, N, J> {
+  fn new() -> Self {
+    Dim { exponents: PhantomData }
+  }
+}
+
+impl<L, M, T, I, Th, N, J> Dimension for Dim<L, M, T, I, Th, N, J>
+where L: Integer, M: Integer, T: Integer, I: Integer, Th: Integer, N: Integer, J: Integer {
+  fn exponents() -> [isize; 7] {
+    [L::to_isize(), M::to_isize(), T::to_isize(), I::to_isize(), Th::to_isize(), N::to_isize(), J::to_isize()]
+  }
+}
+
+// Multiplying two quantities adds the exponents of their dimensions.
+impl<L1, M1, T1, I1, Th1, N1, J1, L2, M2, T2, I2, Th2, N2, J2> Mul<Dim<L2, M2, T2, I2, Th2, N2, J2>>
+for Dim<L1, M1, T1, I1, Th1, N1, J1>
+where L1: Add<L2>, M1: Add<M2>, T1: Add<T2>, I1: Add<I2>, Th1: Add<Th2>, N1: Add<N2>, J1: Add<J2> {
+  type Output = Dim<Sum<L1, L2>, Sum<M1, M2>, Sum<T1, T2>, Sum<I1, I2>, Sum<Th1, Th2>, Sum<N1, N2>, Sum<J1, J2>>;
+  fn mul(self, _: Dim<L2, M2, T2, I2, Th2, N2, J2>) -> Self::Output {
+    Dim::new()
+  }
+}
+
+// Dividing two quantities subtracts the exponents of their dimensions.
+impl<L1, M1, T1, I1, Th1, N1, J1, L2, M2, T2, I2, Th2, N2, J2> Div<Dim<L2, M2, T2, I2, Th2, N2, J2>>
+for Dim<L1, M1, T1, I1, Th1, N1, J1>
+where L1: Sub<L2>, M1: Sub<M2>, T1: Sub<T2>, I1: Sub<I2>, Th1: Sub<Th2>, N1: Sub<N2>, J1: Sub<J2> {
+  type Output = Dim<Diff<L1, L2>, Diff<M1, M2>, Diff<T1, T2>, Diff<I1, I2>, Diff<Th1, Th2>, Diff<N1, N2>, Diff<J1, J2>>;
+  fn div(self, _: Dim<L2, M2, T2, I2, Th2, N2, J2>) -> Self::Output {
+    Dim::new()
+  }
+}
@@ -0,0 +1,255 @@
+//! Formatting of units, eg `1.5 km` or, with the alternate flag, `1.5 kilometers`.
+//!
+//! Values are printed exactly when they have a terminating decimal expansion, and as a fraction such as
+//! `1/3 m` otherwise. A precision (`{:.3}`) rounds the value, halves to even. `{:e}` prints scientific
+//! notation (`1.5e3 m`) and `{:E}` the same with an uppercase exponent (`1.5E3 m`). `Engineering` prints
+//! engineering notation, where the exponent is a multiple of three (`15e3 m`).
+use std::fmt;
+use num::{Integer, One, Signed, Zero};
+use num::bigint::BigInt;
+use num::pow::pow;
+use num::rational::BigRational;
+use Unit;
+use convert::Rounding;
+use storage::Storage;
+
+// The significant digits shown in exponent notation when no precision is given and the value does not
+// terminate.
+const DEFAULT_SIGNIFICANT_DIGITS: usize = 16;
+
+fn ten() -> BigInt {
+  BigInt::from(10)
+}
+
+fn ten_to(exp: usize) -> BigInt {
+  pow(ten(), exp)
+}
+
+/// The plural of a unit's longform, eg `kilometers`. Compound forms such as `meter·second⁻¹` are unchanged.
+pub fn plural(longform: &str) -> String {
+  if longform.is_empty() || longform.contains('·') || !longform.chars().all(|c| c.is_alphabetic() || c == ' ' || c == '-') {
+    return longform.into();
+  }
+  // The last word takes the plural, unless it names a scale, eg `degree Celsius` becomes `degrees Celsius`.
+  let mut words: Vec<String> = longform.split(' ').map(String::from).collect();
+  let index = words.iter().rposition(|word| !word.starts_with(char::is_uppercase)).unwrap_or(words.len() - 1);
+  // Eg `pound-force` becomes `pounds-force`.
+  let (word, rest) = match words[index].find('-') {
+    Some(hyphen) => words[index].split_at(hyphen),
+    None => (&words[index][..], ""),
+  };
+  let word = if word == "foot" {
+    "feet".into()
+  } else if word.ends_with('s') || word.ends_with('z') || word.ends_with('x') {
+    word.into()
+  } else if word.ends_with("ch") || word.ends_with("sh") {
+    format!("{}es", word)
+  } else if word.ends_with('y') && !word.ends_with("ay") && !word.ends_with("ey") {
+    format!("{}ies", &word[..word.len() - 1])
+  } else {
+    format!("{}s", word)
+  };
+  words[index] = format!("{}{}", word, rest);
+  words.join(" ")
+}
+
+// The number of decimal places needed to write the value exactly, if it terminates.
+pub(crate) fn terminating_places(value: &BigRational) -> Option<usize> {
+  let (two, five) = (BigInt::from(2), BigInt::from(5));
+  let mut denominator = value.denom().abs();
+  let (mut twos, mut fives) = (0, 0);
+  while denominator.is_multiple_of(&two) {
+    denominator /= &two;
+    twos += 1;
+  }
+  while denominator.is_multiple_of(&five) {
+    denominator /= &five;
+    fives += 1;
+  }
+  if denominator.is_one() { Some(twos.max(fives)) } else { None }
+}
+
+// Write a non-negative value with exactly `places` decimal places.
+fn decimal(value: &BigRational, places: usize) -> String {
+  let scaled = Rounding::HalfEven.round(&(value * BigRational::from_integer(ten_to(places))));
+  let (integer, fraction) = scaled.div_rem(&ten_to(places));
+  if places == 0 {
+    integer.to_string()
+  } else {
+    format!("{}.{:0>width$}", integer, fraction.to_string(), width = places)
+  }
+}
+
+// Write a non-negative value as `mantissa` and `exponent`, where the exponent is a multiple of `step`.
+fn exponential(value: &BigRational, precision: Option<usize>, step: isize) -> (String, isize) {
+  if value.is_zero() {
+    return (decimal(value, precision.unwrap_or(0)), 0);
+  }
+  let scale = |exponent: isize| if exponent >= 0 {
+    BigRational::from_integer(ten_to(exponent as usize))
+  } else {
+    BigRational::new(BigInt::one(), ten_to(-exponent as usize))
+  };
+  // Find the exponent such that 1 <= value / 10^exponent < 10^step.
+  let mut exponent = Integer::div_floor(&(value.numer().to_string().len() as isize - value.denom().to_string().len() as isize), &step) * step;
+  let upper = scale(step);
+  while value / scale(exponent) >= upper {
+    exponent += step;
+  }
+  while value / scale(exponent) < BigRational::one() {
+    exponent -= step;
+  }
+  let mantissa = value / scale(exponent);
+  let places = match precision {
+    Some(places) => places,
+    None => terminating_places(&mantissa).unwrap_or(DEFAULT_SIGNIFICANT_DIGITS - 1),
+  };
+  // Rounding may carry into the next power, eg 9.99 to two places.
+  let rounded = BigRational::new(Rounding::HalfEven.round(&(&mantissa * BigRational::from_integer(ten_to(places)))), ten_to(places));
+  if rounded >= upper {
+    (decimal(&(rounded / upper), places), exponent + step)
+  } else {
+    (decimal(&rounded, places), exponent)
+  }
+}
+
+// Pad the formatted unit according to the width, fill and alignment of the formatter.
+pub(crate) fn pad(f: &mut fmt::Formatter, formatted: &str) -> fmt::Result {
+  let length = formatted.chars().count();
+  let padding = match f.width() {
+    Some(width) if width > length => width - length,
+    _ => return f.write_str(formatted),
+  };
+  let (before, after) = match f.align() {
+    Some(fmt::Alignment::Left) => (0, padding),
+    Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+    _ => (padding, 0),
+  };
+  let fill = f.fill().to_string();
+  write!(f, "{}{}{}", fill.repeat(before), formatted, fill.repeat(after))
+}
+
+// The form of the unit, pluralized in the alternate form unless the number is one.
+fn with_form(f: &mut fmt::Formatter, shortform: String, longform: String, singular: bool, number: String) -> fmt::Result {
+  let form = if !f.alternate() {
+    shortform
+  } else if singular {
+    longform
+  } else {
+    plural(&longform)
+  };
+  if form.is_empty() {
+    pad(f, &number)
+  } else {
+    pad(f, &format!("{} {}", number, form))
+  }
+}
+
+fn sign(value: &BigRational) -> &'static str {
+  if value.is_negative() { "-" } else { "" }
+}
+
+// Write the value with the forms of its unit, or the storage as is when it is not a finite number.
+pub(crate) fn write_value<S,F>(value: &S, shortform: String, longform: String, f: &mut fmt::Formatter, number: F) -> fmt::Result
+where S: Storage, F: FnOnce(&BigRational, Option<usize>) -> String {
+  match value.to_decimal() {
+    Some(decimal) => {
+      let number = number(&decimal, f.precision());
+      with_form(f, shortform, longform, decimal.abs().is_one(), number)
+    },
+    None => with_form(f, shortform, longform, false, value.to_string()),
+  }
+}
+
+// Write the value exactly if it terminates, or rounded to the precision if one is given.
+pub(crate) fn number(value: &BigRational, precision: Option<usize>) -> String {
+  let magnitude = value.abs();
+  match (precision, terminating_places(&magnitude)) {
+    (Some(places), _) | (None, Some(places)) => format!("{}{}", sign(value), decimal(&magnitude, places)),
+    (None, None) => value.to_string(),
+  }
+}
+
+// Write the value as a mantissa and an exponent that is a multiple of `step`, marked by `e` or `E`.
+fn exponent_number(value: &BigRational, precision: Option<usize>, step: isize, marker: char) -> String {
+  let (mantissa, exponent) = exponential(&value.abs(), precision, step);
+  format!("{}{}{}{}", sign(value), mantissa, marker, exponent)
+}
+
+// Write the value in scientific notation, eg `1.5e3`.
+pub(crate) fn scientific_number(value: &BigRational, precision: Option<usize>) -> String {
+  exponent_number(value, precision, 1, 'e')
+}
+
+// Write the value in scientific notation with an uppercase exponent, eg `1.5E3`.
+pub(crate) fn upper_scientific_number(value: &BigRational, precision: Option<usize>) -> String {
+  exponent_number(value, precision, 1, 'E')
+}
+
+// Write the value in engineering notation, eg `15e3`.
+pub(crate) fn engineering_number(value: &BigRational, precision: Option<usize>) -> String {
+  exponent_number(value, precision, 3, 'e')
+}
+
+/// Format the unit as its value and form, eg `1.5 km`.
+pub fn display<U>(unit: &U, f: &mut fmt::Formatter) -> fmt::Result where U: Unit {
+  write_value(unit.value_ref(), U::shortform(), U::longform(), f, number)
+}
+
+/// Format the unit in scientific notation, eg `1.5e3 m`.
+pub fn scientific<U>(unit: &U, f: &mut fmt::Formatter) -> fmt::Result where U: Unit {
+  write_value(unit.value_ref(), U::shortform(), U::longform(), f, scientific_number)
+}
+
+/// Format the unit in scientific notation with an uppercase exponent, eg `1.5E3 m`.
+pub fn upper_scientific<U>(unit: &U, f: &mut fmt::Formatter) -> fmt::Result where U: Unit {
+  write_value(unit.value_ref(), U::shortform(), U::longform(), f, upper_scientific_number)
+}
+
+/// Format the unit in engineering notation, eg `15e3 m`.
+pub fn engineering<U>(unit: &U, f: &mut fmt::Formatter) -> fmt::Result where U: Unit {
+  write_value(unit.value_ref(), U::shortform(), U::longform(), f, engineering_number)
+}
+
+/// Displays a unit in engineering notation, where the exponent is a multiple of three, eg
+/// `format!("{}", Engineering(&Meter::from(15_000)))` is `15e3 m`. The precision and alternate flags apply as
+/// for the unit itself.
+#[derive(Clone, Copy, Debug)]
+pub struct Engineering<'a, U>(pub &'a U) where U: Unit + 'a;
+
+impl<'a, U> fmt::Display for Engineering<'a, U> where U: Unit {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    engineering(self.0, f)
+  }
+}
+
+#[test]
+fn plural_is_correct() {
+  assert_eq!(plural("kilometer"), "kilometers");
+  assert_eq!(plural("hertz"), "hertz");
+  assert_eq!(plural("henry"), "henries");
+  assert_eq!(plural("degree Celsius"), "degrees Celsius");
+  assert_eq!(plural("degree Celsius difference"), "degree Celsius differences");
+  assert_eq!(plural("astronomical unit"), "astronomical units");
+  assert_eq!(plural("inch"), "inches");
+  assert_eq!(plural("square foot"), "square feet");
+  assert_eq!(plural("pound-force"), "pounds-force");
+  assert_eq!(plural("meter·second⁻¹"), "meter·second⁻¹");
+}
+
+#[test]
+fn decimal_rounds_half_to_even() {
+  assert_eq!(decimal(&BigRational::new(5.into(), 2.into()), 0), "2");
+  assert_eq!(decimal(&BigRational::new(7.into(), 2.into()), 0), "4");
+  assert_eq!(decimal(&BigRational::new(1.into(), 3.into()), 3), "0.333");
+  assert_eq!(decimal(&BigRational::new(1.into(), 20.into()), 3), "0.050");
+}
+
+#[test]
+fn exponential_carries_rounding() {
+  let value = BigRational::new(999.into(), 1.into());
+  assert_eq!(exponential(&value, Some(1), 1), ("1.0".into(), 3));
+  assert_eq!(exponential(&value, Some(1), 3), ("999.0".into(), 0));
+  assert_eq!(exponential(&value, None, 3), ("999".into(), 0));
+  assert_eq!(exponential(&BigRational::new(1.into(), 1000.into()), None, 1), ("1".into(), -3));
+}
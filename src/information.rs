@@ -0,0 +1,179 @@
+//! Units of information, the bit and the byte, and formatting of sizes with binary or decimal prefixes.
+//!
+//! Both units take any prefix, so `Kibi<Byte>` and `Kilo<Byte>` compare exactly. The binary prefixes are only
+//! for units of information.
+//!
+//! Information is dimensionless in the SI, but other dimensionless quantities, such as angles, do not convert
+//! into bits or bytes:
+//!
+//! ```compile_fail
+//! # extern crate si;
+//! # use si::derived::Radian;
+//! # use si::information::Byte;
+//! # use si::quantity::IntoQuantity;
+//! # fn main() {
+//! let _ = Byte::from(Radian::from(16).quantity());
+//! # }
+//! ```
+use std::fmt;
+use num::Signed;
+use num::rational::BigRational;
+#[cfg(test)] use num::bigint::BigInt;
+use {Unit, IntoBase};
+use format::{self, terminating_places};
+use prefix::*;
+use storage::Storage;
+
+registered_units! {
+  generate_base! {
+    name      = Bit,
+    longform  = bit,
+    shortform = bit,
+    dimension = Information,
+    doc       = "A bit is a unit of information, a single binary digit.",
+  }
+
+  generate_base! {
+    name      = Byte,
+    longform  = byte,
+    shortform = B,
+    dimension = Information,
+    factor    = (8, 1),
+    doc       = "A byte is a unit of information, equal to eight bits.",
+  }
+}
+
+// No quantity converts into bits or bytes, so they convert into each other directly.
+impl<S> IntoBase<byte::Byte<S>> for bit::Bit<S> where S: Storage {
+  fn base(self) -> byte::Byte<S> {
+    byte::Byte::new(self.value() / S::from_i64(8))
+  }
+}
+
+impl<S> IntoBase<bit::Bit<S>> for byte::Byte<S> where S: Storage {
+  fn base(self) -> bit::Bit<S> {
+    bit::Bit::new(self.value() * S::from_i64(8))
+  }
+}
+
+/// The prefixes a `HumanSize` chooses from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Prefixes {
+  /// Powers of 1024, eg `KiB` and `MiB`.
+  Binary,
+  /// Powers of 1000, eg `kB` and `MB`.
+  Decimal,
+  /// Whichever of the two writes the size in fewer decimal places, eg `1 KiB` for 1024 bytes but `1 kB` for
+  /// 1000 bytes. Binary is chosen when they tie.
+  Best,
+}
+
+/// A size formatted in bytes with the largest prefix that keeps the value at least one, eg `1.5 MiB`. The
+/// formatting flags apply as for any unit, so `{:.1}` rounds and `{:#}` writes `1.5 mebibytes`.
+#[derive(Clone, Debug)]
+pub struct HumanSize<S = BigRational> where S: Storage {
+  bytes: byte::Byte<S>,
+  prefixes: Prefixes,
+}
+
+/// Format a size with the best of the prefixes, eg 1536 bytes is `1.5 KiB` in binary.
+pub fn human_size<U,S>(size: U, prefixes: Prefixes) -> HumanSize<S> where U: IntoBase<byte::Byte<S>>, S: Storage {
+  HumanSize {
+    bytes: size.base(),
+    prefixes,
+  }
+}
+
+// The prefixes of a kind, from kilo or kibi upwards.
+fn candidates(binary: bool) -> Vec<PrefixKind> {
+  PrefixKind::all().iter().cloned()
+    .filter(|kind| kind.is_binary() == binary && kind.factor() >= PrefixKind::Kilo.factor())
+    .collect()
+}
+
+// The largest prefix no greater than the size, or none if the size is under one of every prefix.
+fn choose(bytes: &BigRational, candidates: Vec<PrefixKind>) -> Option<PrefixKind> {
+  candidates.into_iter().filter(|kind| bytes.abs() >= *kind.factor()).max_by(|a, b| a.factor().cmp(b.factor()))
+}
+
+// The decimal places needed to write the size in the prefix, then the value itself. Non-terminating values
+// sort last.
+fn places(bytes: &BigRational, candidate: Option<PrefixKind>) -> (usize, BigRational) {
+  let value = match candidate {
+    Some(kind) => bytes / kind.factor(),
+    None => bytes.clone(),
+  }.abs();
+  (terminating_places(&value).unwrap_or(usize::MAX), value)
+}
+
+impl<S> fmt::Display for HumanSize<S> where S: Storage {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let bytes = match self.bytes.value_ref().to_decimal() {
+      Some(bytes) => bytes,
+      None => return fmt::Display::fmt(&self.bytes, f),
+    };
+    let (binary, decimal) = (choose(&bytes, candidates(true)), choose(&bytes, candidates(false)));
+    let candidate = match self.prefixes {
+      Prefixes::Binary => binary,
+      Prefixes::Decimal => decimal,
+      Prefixes::Best if places(&bytes, decimal) < places(&bytes, binary) => decimal,
+      Prefixes::Best => binary,
+    };
+    match candidate {
+      Some(kind) => {
+        let (shortform, longform) = (format!("{}{}", kind.symbol(), Byte::shortform()), format!("{}{}", kind.name(), Byte::longform()));
+        format::write_value(&(bytes / kind.factor()), shortform, longform, f, format::number)
+      },
+      None => fmt::Display::fmt(&Byte::from(bytes), f),
+    }
+  }
+}
+
+#[test]
+fn bytes_are_eight_bits() {
+  assert_eq!(IntoBase::<Byte>::base(Bit::from(12)), Byte::from(3) / 2);
+  assert_eq!(IntoBase::<Bit>::base(Byte::from(Kilo::<Byte>::from(1))), Kilo::<Bit>::from(8));
+  assert_eq!(Kibi::<Byte>::from(1), Byte::from(1_024));
+}
+
+#[test]
+fn binary_and_decimal_prefixes_compare_exactly() {
+  assert!(Kibi::<Byte>::from(1) > Kilo::<Byte>::from(1));
+  assert!(Mebi::<Byte>::from(1_000) < Giga::<Byte>::from(1) * 2);
+  assert_eq!(Kibi::<Byte>::from(1_000), Kilo::<Byte>::from(1_024));
+}
+
+#[test]
+fn human_size_picks_prefix() {
+  let size = |bytes: i64, prefixes| format!("{}", human_size(Byte::from(bytes), prefixes));
+  assert_eq!(size(1_536, Prefixes::Binary), "1.5 KiB");
+  assert_eq!(size(1_536, Prefixes::Decimal), "1.536 kB");
+  assert_eq!(size(1_000, Prefixes::Binary), "1000 B");
+  assert_eq!(size(1_000, Prefixes::Best), "1 kB");
+  assert_eq!(size(1_024, Prefixes::Best), "1 KiB");
+  assert_eq!(size(3 * 1_024 * 1_024 * 1_024, Prefixes::Best), "3 GiB");
+  assert_eq!(format!("{:#.1}", human_size(Byte::from(1_500_000), Prefixes::Binary)), "1.4 mebibytes");
+  assert_eq!(format!("{}", human_size(Bit::from(12_000), Prefixes::Decimal)), "1.5 kB");
+  assert_eq!(format!("{}", human_size(Kilo::<Byte>::from(BigInt::from(10).pow(28)), Prefixes::Decimal)), "10 QB");
+}
+
+#[test]
+fn can_parse_binary_prefixes() {
+  assert_eq!("1.5 KiB".parse::<Byte>(), Ok(Byte::from(1_536)));
+  assert_eq!("2 MiB".parse::<Kibi<Byte>>(), Ok(Kibi::<Byte>::from(2_048)));
+  assert_eq!("8 kbit".parse::<Bit>(), Ok(Bit::from(8_000)));
+}
+
+#[test]
+fn binary_prefixes_are_only_for_information() {
+  use base::Meter;
+  use derived::Newton;
+  use registry::lookup;
+  assert!("1 Kim".parse::<Meter>().is_err());
+  assert!("1 KiN".parse::<Newton>().is_err());
+  assert!(lookup("KiN").is_err());
+  assert_eq!(lookup("Kibit").unwrap().prefix, Some(PrefixKind::Kibi));
+  let preferred = Normalization::Preferred(vec![PrefixKind::Kibi]);
+  assert_eq!(normalize(Meter::from(2_048), &preferred).prefix(), None);
+  assert_eq!(normalize::<_, Byte>(Byte::from(2_048), &preferred).prefix(), Some(PrefixKind::Kibi));
+}
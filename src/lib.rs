@@ -1,18 +1,37 @@
-extern crate num;
+pub extern crate num;
+#[doc(hidden)] pub extern crate typenum;
 
-#[macro_use] extern crate lazy_static;
+#[doc(hidden)] #[macro_use] pub extern crate lazy_static;
 
 #[cfg(test)] #[macro_use] extern crate quickcheck;
+#[cfg(feature = "serde")] #[doc(hidden)] pub extern crate serde;
+#[cfg(all(test, feature = "serde"))] extern crate serde_json;
 
 pub mod prefix;
-pub mod base;
+#[macro_use] pub mod base;
+#[macro_use] pub mod derived;
+pub mod temperature;
+pub mod accepted;
+pub mod customary;
+pub mod information;
 pub mod dimension;
+pub mod format;
+pub mod parse;
+pub mod quantity;
+pub mod storage;
+pub mod total;
+pub mod convert;
+pub mod constants;
+pub mod registry;
+#[cfg(feature = "serde")] pub mod serialization;
 
+use std::convert::TryFrom;
 use std::ops::*;
 use num::bigint::BigInt;
 use num::rational::BigRational;
+use storage::Storage;
 
-pub trait Unit: Clone + Eq 
+pub trait Unit: Clone + PartialEq
 + From<BigRational> + From<BigInt>
 + From<i64> + From<u64> + From<i32> + From<u32> + From<usize> + From<isize>
 + From<i16> + From<u16> + From<i8> + From <u8>
@@ -23,19 +42,79 @@ pub trait Unit: Clone + Eq
 + Div<BigRational> + Div<BigInt>
 + Div<i64> + Div<u64> + Div<i32> + Div<u32> + Div<usize> + Div<isize>
 + Div<i16> + Div<u16> + Div<i8> + Div <u8> {
+  /// The numeric type holding the value, `BigRational` unless chosen otherwise.
+  type Storage: Storage;
   /// Create a new unit from a numeric value.
-  fn new(val: BigRational) -> Self;
+  fn new(val: Self::Storage) -> Self;
   /// Get the value. 
-  fn value(self) -> BigRational;
+  fn value(self) -> Self::Storage;
   /// Get a reference to the value.
-  fn value_ref(&self) -> &BigRational;
+  fn value_ref(&self) -> &Self::Storage;
   /// The full string for the unit. Eg `kilometer` for Kilometer.
   fn longform() -> String;
   /// The short hand for the unit. Eg `km` for Kilometer.
   fn shortform() -> String;
+  /// Create a unit from an exact number such as `0.1`, `-1.5e3` or `3/4`, so `0.1` is exactly a tenth rather
+  /// than the nearest float.
+  fn from_decimal(input: &str) -> Result<Self, parse::ParseError> {
+    parse::parse_number(input).map(Self::from)
+  }
+  /// The value as the nearest `f64`, or `None` if it is not a finite number. Values too large for an `f64`
+  /// are infinite.
+  fn to_f64(&self) -> Option<f64> {
+    convert::nearest_f64(self.value_ref())
+  }
+  /// The value as the nearest `f32`, or `None` if it is not a finite number. Values too large for an `f32`
+  /// are infinite.
+  fn to_f32(&self) -> Option<f32> {
+    convert::nearest_f32(self.value_ref())
+  }
+  /// Round to a whole number of this unit, eg whole millimeters with `Milli<Meter>`. Values that are not
+  /// finite numbers are unchanged.
+  fn round(self, rounding: convert::Rounding) -> Self {
+    convert::map(self, |value| BigRational::from_integer(rounding.round(&value)))
+  }
+  /// Round to the nearest multiple of the step, halves to even, eg to the nearest 5 mm. Values and steps
+  /// that are not finite numbers leave the value unchanged.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the step is zero.
+  fn round_to(self, step: Self) -> Self {
+    match step.value_ref().to_rational() {
+      Some(step) => convert::map(self, |value| convert::round_to(value, &step)),
+      None => self,
+    }
+  }
+  /// The closest value with a denominator of at most `max_denominator`, eg 355/113 for pi within 1000.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `max_denominator` is less than one.
+  fn approximate<I>(self, max_denominator: I) -> Self where I: Into<BigInt> {
+    let max_denominator = max_denominator.into();
+    convert::map(self, |value| convert::approximate(value, &max_denominator))
+  }
+  /// Round to the given number of significant decimal digits, halves to even, eg 1.2345 to 1.23.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `digits` is zero.
+  fn limit_significant_digits(self, digits: usize) -> Self {
+    convert::map(self, |value| convert::significant_digits(value, digits))
+  }
+  /// Round to an integer of this unit, or an error if it is not a finite number or does not fit.
+  fn to_integer<I>(&self, rounding: convert::Rounding) -> Result<I, convert::ConversionError> where I: TryFrom<BigInt> {
+    convert::rounded(self.value_ref(), rounding)
+  }
 }
 
-pub trait IntoBase<B>: Unit + Add<Self,Output=Self> + Sub<Self,Output=Self> where B: Unit {
+pub trait IntoBase<B>: Unit<Storage=<B as Unit>::Storage> + Add<Self,Output=Self> + Sub<Self,Output=Self> where B: Unit {
   /// Get the base unit.
   fn base(self) -> B;
+  /// The prefix of the unit, if it is one, so that converting between two prefixes can scale by a
+  /// precomputed ratio rather than going through the base.
+  fn prefix_kind() -> Option<prefix::PrefixKind> {
+    None
+  }
 }
\ No newline at end of file
@@ -0,0 +1,230 @@
+//! Parsing of units from strings such as `1500 m`, `1.5km`, `3/4 mm` or `2e-6 s`.
+use std::error::Error;
+use std::fmt;
+use num::{One, Zero};
+use num::bigint::BigInt;
+use num::pow::pow;
+use num::rational::BigRational;
+use prefix::Prefixable;
+use format::plural;
+use prefix::*;
+use Unit;
+#[cfg(test)] use base::*;
+
+/// An error encountered while parsing a unit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+  /// The input did not start with a number, eg `km`.
+  InvalidNumber(String),
+  /// The number had a denominator of zero, eg `1/0 m`.
+  ZeroDenominator,
+  /// The number was not followed by a unit.
+  MissingUnit {
+    expected: String,
+  },
+  /// The unit was not the expected unit or a prefix of it.
+  UnknownUnit {
+    found: String,
+    expected: String,
+  },
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      ParseError::InvalidNumber(ref input) => write!(f, "`{}` does not start with a number", input),
+      ParseError::ZeroDenominator => write!(f, "the denominator cannot be zero"),
+      ParseError::MissingUnit { ref expected } => write!(f, "expected a unit of `{}` after the number", expected),
+      ParseError::UnknownUnit { ref found, ref expected } => write!(f, "`{}` is not a unit of `{}`", found, expected),
+    }
+  }
+}
+
+impl Error for ParseError {}
+
+// The length of the run of ASCII digits at the start of the input.
+fn digits(input: &[u8]) -> usize {
+  input.iter().take_while(|c| c.is_ascii_digit()).count()
+}
+
+/// Split the input into the number at its start and the remainder.
+fn split_number(input: &str) -> (&str, &str) {
+  let bytes = input.as_bytes();
+  let mut end = 0;
+  if end < bytes.len() && (bytes[end] == b'-' || bytes[end] == b'+') {
+    end += 1;
+  }
+  end += digits(&bytes[end..]);
+  if end < bytes.len() && bytes[end] == b'.' {
+    end += 1 + digits(&bytes[end + 1..]);
+  }
+  // An exponent is only taken when digits follow it, so `5Em` is five exameters.
+  if end < bytes.len() && (bytes[end] == b'e' || bytes[end] == b'E') {
+    let sign = if end + 1 < bytes.len() && (bytes[end + 1] == b'-' || bytes[end + 1] == b'+') { 1 } else { 0 };
+    let exponent = digits(&bytes[end + 1 + sign..]);
+    if exponent > 0 {
+      end += 1 + sign + exponent;
+    }
+  }
+  if end < bytes.len() && bytes[end] == b'/' {
+    let denominator = digits(&bytes[end + 1..]);
+    if denominator > 0 {
+      end += 1 + denominator;
+    }
+  }
+  input.split_at(end)
+}
+
+// The largest exponent of a number, so that untrusted input such as `1e5000000` cannot take unbounded time
+// and memory. The digits after the point are not limited, as each of them is already in the input.
+const MAX_EXPONENT: u64 = 10_000;
+
+/// Parse an exact number such as `1500`, `-1.5`, `3/4` or `2e-6`, with any number of digits. Numbers with
+/// an exponent beyond ±10000, eg `1e10001`, are invalid.
+pub fn parse_number(input: &str) -> Result<BigRational, ParseError> {
+  let invalid = || ParseError::InvalidNumber(input.into());
+  let (number, rest) = split_number(input.trim());
+  if !rest.is_empty() {
+    return Err(invalid());
+  }
+  let (number, denominator) = match number.find('/') {
+    Some(index) => (&number[..index], number[index + 1..].parse::<BigInt>().map_err(|_| invalid())?),
+    None => (number, BigInt::one()),
+  };
+  if denominator.is_zero() {
+    return Err(ParseError::ZeroDenominator);
+  }
+  let (mantissa, exponent) = match number.find(['e', 'E']) {
+    Some(index) => (&number[..index], number[index + 1..].parse::<i64>().map_err(|_| invalid())?),
+    None => (number, 0),
+  };
+  let (integer, fraction) = match mantissa.find('.') {
+    Some(index) => (&mantissa[..index], &mantissa[index + 1..]),
+    None => (mantissa, ""),
+  };
+  let (sign, integer) = match integer.chars().next() {
+    Some('-') => (-BigInt::one(), &integer[1..]),
+    Some('+') => (BigInt::one(), &integer[1..]),
+    _ => (BigInt::one(), integer),
+  };
+  if integer.is_empty() && fraction.is_empty() {
+    return Err(invalid());
+  }
+  // `1.25e-3` is 125 * 10^(-3 - 2).
+  let digits = format!("{}{}", integer, fraction).parse::<BigInt>().map_err(|_| invalid())?;
+  if exponent.unsigned_abs() > MAX_EXPONENT {
+    return Err(invalid());
+  }
+  let exponent = exponent - fraction.len() as i64;
+  let ten = BigInt::from(10);
+  let scale = pow(ten, exponent.unsigned_abs() as usize);
+  let value = if exponent >= 0 {
+    BigRational::from_integer(sign * digits * scale)
+  } else {
+    BigRational::new(sign * digits, scale)
+  };
+  Ok(value / BigRational::from_integer(denominator))
+}
+
+// Whether the symbol names the unit, by its shortform, longform or plural longform.
+fn names<U>(symbol: &str) -> bool where U: Unit {
+  names_forms(symbol, &U::shortform(), &U::longform())
+}
+
+pub(crate) fn names_forms(symbol: &str, shortform: &str, longform: &str) -> bool {
+  // Accept the micro sign (U+00B5) as well as the Greek mu used by `Micro`.
+  let symbol = symbol.replace('\u{b5}', "\u{3bc}");
+  symbol == shortform || symbol == longform || symbol == plural(longform)
+}
+
+// The factor from the named unit to the base, if the symbol is the base or a prefix of it.
+fn factor_to_base<B>(symbol: &str) -> Option<BigRational> where B: Prefixable {
+  if names::<B>(symbol) {
+    return Some(BigRational::one());
+  }
+  PrefixKind::all().iter()
+    .filter(|kind| B::takes_binary_prefixes() || !kind.is_binary())
+    .find(|kind| names_forms(symbol, &format!("{}{}", kind.symbol(), B::shortform()), &format!("{}{}", kind.name(), B::longform())))
+    .map(|kind| kind.factor().clone())
+}
+
+// Split the input into its number and symbol.
+fn split(input: &str) -> Result<(BigRational, &str), ParseError> {
+  let input = input.trim();
+  let (number, symbol) = split_number(input);
+  match parse_number(number) {
+    Ok(value) => Ok((value, symbol.trim())),
+    Err(ParseError::InvalidNumber(_)) => Err(ParseError::InvalidNumber(input.into())),
+    Err(error) => Err(error),
+  }
+}
+
+/// Parse a unit measured in `B` or any prefix of it, converting to `U`. Eg `1.5km` parses as 1500 meters.
+pub fn parse<U,B>(input: &str) -> Result<U, ParseError> where U: Unit + From<B>, B: Prefixable {
+  let (value, symbol) = split(input)?;
+  if symbol.is_empty() {
+    return Err(ParseError::MissingUnit { expected: U::shortform() });
+  }
+  match factor_to_base::<B>(symbol) {
+    Some(factor) => Ok(U::from(B::from(value * factor))),
+    None => Err(ParseError::UnknownUnit { found: symbol.into(), expected: U::shortform() }),
+  }
+}
+
+/// Parse a unit measured only in `U` itself, eg `5 N`.
+pub fn parse_exact<U>(input: &str) -> Result<U, ParseError> where U: Unit {
+  parse_value(input, &U::shortform(), &U::longform()).map(U::from)
+}
+
+// Parse a value followed by the given forms of its unit.
+pub(crate) fn parse_value(input: &str, shortform: &str, longform: &str) -> Result<BigRational, ParseError> {
+  let (value, symbol) = split(input)?;
+  if names_forms(symbol, shortform, longform) {
+    Ok(value)
+  } else if symbol.is_empty() {
+    Err(ParseError::MissingUnit { expected: shortform.into() })
+  } else {
+    Err(ParseError::UnknownUnit { found: symbol.into(), expected: shortform.into() })
+  }
+}
+
+#[test]
+fn can_parse_numbers() {
+  let ratio = |n: i64, d: i64| BigRational::new(n.into(), d.into());
+  assert_eq!(parse_number("1500"), Ok(ratio(1500, 1)));
+  assert_eq!(parse_number("-1.5"), Ok(ratio(-3, 2)));
+  assert_eq!(parse_number("3/4"), Ok(ratio(3, 4)));
+  assert_eq!(parse_number("2e-6"), Ok(ratio(1, 500_000)));
+  assert_eq!(parse_number("1.5E3"), Ok(ratio(1500, 1)));
+  assert_eq!(parse_number(".5"), Ok(ratio(1, 2)));
+  assert_eq!(parse_number("1/0"), Err(ParseError::ZeroDenominator));
+  assert_eq!(parse_number("abc"), Err(ParseError::InvalidNumber("abc".into())));
+  assert_eq!(parse_number("-"), Err(ParseError::InvalidNumber("-".into())));
+  assert_eq!(parse_number("1e10000"), Ok(BigRational::from_integer(pow(BigInt::from(10), 10_000))));
+  assert_eq!(parse_number("1e10001"), Err(ParseError::InvalidNumber("1e10001".into())));
+  assert_eq!(parse_number("1e-10001"), Err(ParseError::InvalidNumber("1e-10001".into())));
+  assert_eq!(parse_number("1.5e-10000"), Ok(ratio(3, 2) / BigRational::from_integer(pow(BigInt::from(10), 10_000))));
+}
+
+#[test]
+fn takes_any_number_of_digits() {
+  let fraction = "5".repeat(10_001);
+  let expected = BigRational::new(fraction.parse().unwrap(), pow(BigInt::from(10), 10_001));
+  assert_eq!(parse_number(&format!("0.{}", fraction)), Ok(expected.clone()));
+  assert_eq!(format!("0.{}e3 km", fraction).parse::<Meter>(), Ok(Meter::from(expected * BigRational::from_integer(1_000_000.into()))));
+}
+
+#[test]
+fn rejects_huge_exponents() {
+  assert_eq!("1e5000000 m".parse::<Meter>(), Err(ParseError::InvalidNumber("1e5000000 m".into())));
+  assert_eq!("1.5e-9223372036854775808 m".parse::<Meter>(), Err(ParseError::InvalidNumber("1.5e-9223372036854775808 m".into())));
+  assert!(Meter::from_decimal("1e99999999999999999999").is_err());
+}
+
+#[test]
+fn can_split_numbers() {
+  assert_eq!(split_number("1.5km"), ("1.5", "km"));
+  assert_eq!(split_number("5Em"), ("5", "Em"));
+  assert_eq!(split_number("2e-6 s"), ("2e-6", " s"));
+  assert_eq!(split_number("3/4 mm"), ("3/4", " mm"));
+}
@@ -0,0 +1,116 @@
+use std::fmt;
+use num::rational::BigRational;
+
+macro_rules! prefix_kinds {
+  {
+    $($name:ident => $module:ident,)*
+  } => (
+    /// Every prefix, for choosing one at runtime rather than by type. Eg `PrefixKind::Kilo` is the `Kilo`
+    /// prefix, with the symbol `k` and a factor of 10^3. They are ordered as in `PrefixKind::all()`.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub enum PrefixKind {
+      $(
+        #[doc = concat!("The ", stringify!($module), " prefix.")]
+        $name,
+      )*
+    }
+
+    static ALL: &[PrefixKind] = &[$(PrefixKind::$name,)*];
+
+    impl PrefixKind {
+      /// The symbol of the prefix, eg `k` for kilo.
+      pub fn symbol(self) -> &'static str {
+        match self {
+          $(PrefixKind::$name => &super::$module::SHORTFORM,)*
+        }
+      }
+      /// The name of the prefix, eg `kilo`.
+      pub fn name(self) -> &'static str {
+        match self {
+          $(PrefixKind::$name => &super::$module::LONGFORM,)*
+        }
+      }
+      /// The factor of the prefix, eg 10^3 for kilo.
+      pub fn factor(self) -> &'static BigRational {
+        match self {
+          $(PrefixKind::$name => super::$module::FACTOR.exact(),)*
+        }
+      }
+    }
+  )
+}
+
+prefix_kinds! {
+  Quetta => quetta,
+  Ronna => ronna,
+  Yotta => yotta,
+  Zetta => zetta,
+  Exa => exa,
+  Peta => peta,
+  Tera => tera,
+  Giga => giga,
+  Mega => mega,
+  Kilo => kilo,
+  Hecto => hecto,
+  Deca => deca,
+  Deci => deci,
+  Centi => centi,
+  Milli => milli,
+  Micro => micro,
+  Nano => nano,
+  Pico => pico,
+  Femto => femto,
+  Atto => atto,
+  Zepto => zepto,
+  Yocto => yocto,
+  Ronto => ronto,
+  Quecto => quecto,
+  Kibi => kibi,
+  Mebi => mebi,
+  Gibi => gibi,
+  Tebi => tebi,
+  Pebi => pebi,
+  Exbi => exbi,
+  Zebi => zebi,
+  Yobi => yobi,
+}
+
+impl PrefixKind {
+  /// Every prefix, the SI prefixes from largest to smallest and then the binary prefixes.
+  pub fn all() -> &'static [PrefixKind] {
+    ALL
+  }
+  /// Find the prefix with the symbol, accepting the micro sign `µ` as well as `μ`.
+  pub fn from_symbol(symbol: &str) -> Option<PrefixKind> {
+    let symbol = symbol.replace('\u{b5}', "\u{3bc}");
+    ALL.iter().cloned().find(|kind| kind.symbol() == symbol)
+  }
+  /// Whether it is one of the binary prefixes, which are powers of 1024 rather than of 10.
+  pub fn is_binary(self) -> bool {
+    self >= PrefixKind::Kibi
+  }
+}
+
+impl fmt::Display for PrefixKind {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.pad(self.symbol())
+  }
+}
+
+#[test]
+fn looks_up_symbols() {
+  assert_eq!(PrefixKind::from_symbol("k"), Some(PrefixKind::Kilo));
+  assert_eq!(PrefixKind::from_symbol("µ"), Some(PrefixKind::Micro));
+  assert_eq!(PrefixKind::from_symbol("Q"), Some(PrefixKind::Quetta));
+  assert_eq!(PrefixKind::from_symbol("Ki"), Some(PrefixKind::Kibi));
+  assert_eq!(PrefixKind::from_symbol("K"), None);
+}
+
+#[test]
+fn lists_every_prefix() {
+  assert_eq!(PrefixKind::all().len(), 32);
+  assert!(PrefixKind::all().iter().all(|kind| PrefixKind::from_symbol(kind.symbol()) == Some(*kind)));
+  assert_eq!(PrefixKind::all().iter().filter(|kind| kind.is_binary()).count(), 8);
+  assert_eq!(PrefixKind::Ronto.name(), "ronto");
+  assert_eq!(format!("{}", PrefixKind::Quecto), "q");
+}
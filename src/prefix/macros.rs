@@ -1,13 +1,48 @@
 #[macro_use]
 macro_rules! prefix_from_primitives { 
   {
-    $unit:ident, 
+    $unit:ident,
+    $bound:ident,
     [$($primitive:ty,)*]
   } => {
     $(
-      impl<B> From<$primitive> for $unit<B> where B: Base {
+      impl<B> From<$primitive> for $unit<B> where B: $bound {
         fn from(value: $primitive) -> Self {
-          Self::from(BigInt::from(value))
+          Self::new(::storage::from_integer(value))
+        }
+      }
+    )*
+  }
+}
+
+macro_rules! prefix_from_floats {
+  {
+    $unit:ident,
+    $bound:ident,
+    [$($float:ty,)*]
+  } => {
+    $(
+      impl<B> ::std::convert::TryFrom<$float> for $unit<B> where B: $bound {
+        type Error = ::convert::ConversionError;
+        fn try_from(value: $float) -> Result<Self, Self::Error> {
+          ::convert::exact(value.into()).map(Self::from)
+        }
+      }
+    )*
+  }
+}
+
+macro_rules! prefix_into_integers {
+  {
+    $unit:ident,
+    $bound:ident,
+    [$($primitive:ty,)*]
+  } => {
+    $(
+      impl<B> ::std::convert::TryFrom<$unit<B>> for $primitive where B: $bound {
+        type Error = ::convert::ConversionError;
+        fn try_from(unit: $unit<B>) -> Result<Self, Self::Error> {
+          ::convert::integer(&unit.value)
         }
       }
     )*
@@ -17,27 +52,103 @@ macro_rules! prefix_from_primitives {
 #[macro_use]
 macro_rules! prefix_div_and_mul_with_primitives { 
   {
-    $unit:ident, 
+    $unit:ident,
+    $bound:ident,
     [$($primitive:ty,)*]
   } => {
     $(
-      impl<B> Div<$primitive> for $unit<B> where B: Base {
+      impl<B> Div<$primitive> for $unit<B> where B: $bound {
         type Output = Self;
         fn div(self, value: $primitive) -> Self {
-          self / BigRational::from_integer(BigInt::from(value))
+          Self::new(self.value / ::storage::from_integer(value))
         }
       }
 
-      impl<B> Mul<$primitive> for $unit<B> where B: Base {
+      impl<B> Mul<$primitive> for $unit<B> where B: $bound {
         type Output = Self;
         fn mul(self, value: $primitive) -> Self {
-          self * BigRational::from_integer(BigInt::from(value))
+          Self::new(self.value * ::storage::from_integer(value))
+        }
+      }
+    )*
+  }
+}
+
+// Scalars in the storage of the base multiply and divide the value directly, on either side.
+macro_rules! prefix_div_and_mul_with_storage {
+  {
+    $unit:ident,
+    $bound:ident,
+    [$($storage:ty,)*]
+  } => {
+    $(
+      impl<B> Div<$storage> for $unit<B> where B: $bound<Storage=$storage> {
+        type Output = Self;
+        fn div(self, value: $storage) -> Self {
+          Self::new(self.value / value)
+        }
+      }
+
+      impl<B> Mul<$storage> for $unit<B> where B: $bound<Storage=$storage> {
+        type Output = Self;
+        fn mul(self, value: $storage) -> Self {
+          Self::new(self.value * value)
+        }
+      }
+
+      impl<B> Mul<$unit<B>> for $storage where B: $bound<Storage=$storage> {
+        type Output = $unit<B>;
+        fn mul(self, unit: $unit<B>) -> $unit<B> {
+          unit * self
+        }
+      }
+    )*
+  }
+}
+
+macro_rules! prefix_scalar_mul {
+  {
+    $unit:ident,
+    $bound:ident,
+    [$($scalar:ty,)*]
+  } => {
+    $(
+      impl<B> Mul<$unit<B>> for $scalar where B: $bound {
+        type Output = $unit<B>;
+        fn mul(self, unit: $unit<B>) -> $unit<B> {
+          unit * self
         }
       }
     )*
   }
 }
 
+// Prefixed grams also normalise to the kilogram, the SI base unit of mass. Binary prefixes do not apply to
+// grams.
+macro_rules! prefix_mass {
+  {
+    $name:ident,
+    Prefixable
+  } => {
+    impl<S> IntoBase<Kilo<::base::gram::Gram<S>>> for $name<::base::gram::Gram<S>> where S: Storage {
+      fn base(self) -> Kilo<::base::gram::Gram<S>> {
+        Kilo::new(self.quantity().value())
+      }
+    }
+
+    #[cfg(test)]
+    quickcheck! {
+      fn quantity_of_mass_is_in_kilograms(value: $name<::base::Gram>) -> bool {
+        let grams: ::base::Gram = value.clone().base();
+        value.quantity().value() == grams.value() / BigRational::from_integer(1000.into())
+      }
+    }
+  };
+  {
+    $name:ident,
+    BinaryPrefixable
+  } => {}
+}
 
 #[macro_use]
 macro_rules! generate_prefix {
@@ -47,38 +158,87 @@ macro_rules! generate_prefix {
     shortform = $shortform:ident,
     factor = $factor:expr,
     $doc:meta,
+  } => (
+    generate_prefix! {
+      @factor   = ::prefix::generate_prefix_factor($factor),
+      @bound    = Prefixable,
+      @test     = ::base::Meter,
+      name      = $name,
+      longform  = $longform,
+      shortform = $shortform,
+      $doc,
+    }
+  );
+  {
+    name   = $name:ident,
+    longform = $longform:ident,
+    shortform = $shortform:ident,
+    binary = $binary:expr,
+    $doc:meta,
+  } => (
+    generate_prefix! {
+      @factor   = ::prefix::generate_binary_prefix_factor($binary),
+      @bound    = BinaryPrefixable,
+      @test     = ::information::Bit,
+      name      = $name,
+      longform  = $longform,
+      shortform = $shortform,
+      $doc,
+    }
+  );
+  {
+    @factor = $factor:expr,
+    @bound = $bound:ident,
+    @test = $test:path,
+    name   = $name:ident,
+    longform = $longform:ident,
+    shortform = $shortform:ident,
+    $doc:meta,
   } => (
     mod $longform {
-      #[cfg(test)] use base::Meter;
+      // The unit the tests prefix, the meter for SI prefixes and the bit for binary ones.
+      #[cfg(test)] use $test as Measured;
+      #[cfg(test)] use base::Second;
       #[cfg(test)] use quickcheck::{Arbitrary, Gen};
       use {Unit, IntoBase};
-      use base::Base;
       use dimension::*;
       use num::bigint::BigInt;
       use num::rational::BigRational;
       use prefix::*;
+      use quantity::{Quantity, IntoQuantity, QuantityKind};
+      use format;
+      use parse::{self, ParseError};
+      use std::fmt;
+      use std::cmp::Ordering;
+      use std::iter::Sum;
       use std::marker::PhantomData;
       use std::ops::*;
-      use super::generate_prefix_factor;
+      use std::str::FromStr;
+      use storage::{take, zero, Factor, Storage};
 
       lazy_static! {
-        static ref FACTOR: BigRational = generate_prefix_factor($factor);
-        static ref SHORTFORM: &'static str = stringify!($shortform);
-        static ref LONGFORM: &'static str = stringify!($longform);
+        pub(super) static ref FACTOR: Factor = Factor::new($factor);
+        static ref INVERSE_FACTOR: Factor = FACTOR.recip();
+        // The ratio of the factor of each prefix to this one, to convert a value in that prefix into this one.
+        static ref RATIOS: Vec<Factor> = PrefixKind::all().iter().map(|kind| Factor::new(kind.factor() / FACTOR.exact())).collect();
+        static ref INVERSE_RATIOS: Vec<Factor> = RATIOS.iter().map(Factor::recip).collect();
+        pub(super) static ref SHORTFORM: &'static str = stringify!($shortform);
+        pub(super) static ref LONGFORM: &'static str = stringify!($longform);
       }
 
       #[$doc]
-      #[derive(Clone, Debug, Eq)]
-      pub struct $name<B> where B: Base {
-        value: BigRational,
+      #[derive(Clone, Debug)]
+      pub struct $name<B> where B: $bound {
+        value: B::Storage,
         base: PhantomData<B>,
       }
 
       // Basic impl
-      impl<B> Unit for $name<B> where B: Base {
-        fn new(value: BigRational) -> Self {
+      impl<B> Unit for $name<B> where B: $bound {
+        type Storage = B::Storage;
+        fn new(value: B::Storage) -> Self {
           $name {
-            value: value,
+            value,
             base: PhantomData,
           }
         }
@@ -88,39 +248,44 @@ macro_rules! generate_prefix {
         fn longform() -> String {
           format!("{}{}", *LONGFORM, B::longform())
         }
-        fn value(self) -> BigRational {
+        fn value(self) -> B::Storage {
           self.value
         }
-        fn value_ref(&self) -> &BigRational {
+        fn value_ref(&self) -> &B::Storage {
           &self.value
         }
       }
 
       #[test]
       fn has_right_shortform() {
-        assert_eq!($name::<Meter>::shortform(), format!("{}{}", stringify!($shortform), Meter::shortform()))
+        assert_eq!($name::<Measured>::shortform(), format!("{}{}", stringify!($shortform), Measured::shortform()))
       }
       #[test]
       fn has_right_longform() {
-        assert_eq!($name::<Meter>::longform(), format!("{}{}", stringify!($longform), Meter::longform()))
+        assert_eq!($name::<Measured>::longform(), format!("{}{}", stringify!($longform), Measured::longform()))
+      }
+      #[test]
+      fn has_right_kind() {
+        let kind = $name::<Measured>::kind();
+        assert_eq!((kind.symbol(), kind.name(), kind.factor()), (stringify!($shortform), stringify!($longform), FACTOR.exact()));
       }
 
       #[cfg(test)]
       quickcheck! {
-        fn value_functions_as_expected(value: $name<Meter>) -> bool {
-          let expected_amount = value.clone().base().value() / $name::<Meter>::factor();
+        fn value_functions_as_expected(value: $name<Measured>) -> bool {
+          let expected_amount = value.clone().base().value() / $name::<Measured>::factor();
           value.value() == expected_amount
         }
       }
 
       #[cfg(test)]
-      impl<B> Arbitrary for $name<B> where B: Base + Send + 'static {
+      impl<B> Arbitrary for $name<B> where B: $bound + Send + 'static, B::Storage: Send {
         fn arbitrary<G: Gen>(g: &mut G) -> Self {
           let (numerator, denominator) = (g.gen::<i64>(), g.gen::<i64>());
           let denominator = if denominator == 0 { 1 } else { denominator }; // The denominator cannot be zero.
 
           let rational = BigRational::new(BigInt::from(numerator), BigInt::from(denominator));
-          $name::<B>::new(rational)
+          $name::<B>::from(rational)
         }
       }
 
@@ -129,67 +294,127 @@ macro_rules! generate_prefix {
       //
 
       // Enable it to be a prefix.
-      impl<B> Prefix<B> for $name<B> where B: Base {
+      impl<B> Prefix<B> for $name<B> where B: $bound {
         fn factor() -> &'static BigRational {
-          &*FACTOR
+          FACTOR.exact()
+        }
+        fn kind() -> PrefixKind {
+          PrefixKind::$name
+        }
+        fn ratio<P>() -> &'static Factor where P: Prefix<B> {
+          &RATIOS[P::kind() as usize]
         }
         fn scale<P>(value: P) -> Self where P: IntoBase<B> {
           Self::from(value.base())
         }
       }
 
-      impl<B> IntoBase<B> for $name<B> where B: Base {
+      impl<B> IntoBase<B> for $name<B> where B: $bound {
         fn base(self) -> B {
-          B::new(self.value * Self::factor())
+          B::new(self.value.scale_by(&FACTOR))
+        }
+        fn prefix_kind() -> Option<PrefixKind> {
+          Some(PrefixKind::$name)
+        }
+      }
+
+      // Measure another unit of the base in this prefix. Other prefixes scale once by a precomputed ratio.
+      fn measure<P,B>(other: P) -> B::Storage where P: IntoBase<B>, B: $bound {
+        match P::prefix_kind() {
+          Some(kind) => other.value().scale_by(&RATIOS[kind as usize]),
+          None => other.base().value().scale_by(&INVERSE_FACTOR),
+        }
+      }
+
+      // Both values measured in the smaller of this prefix and another unit of the base, so neither is rounded
+      // for integer storage.
+      fn in_common<P,B>(this: &$name<B>, other: &P) -> (B::Storage, B::Storage) where P: IntoBase<B>, B: $bound {
+        match P::prefix_kind().map(|kind| kind as usize) {
+          Some(index) if RATIOS[index].exact().numer() >= RATIOS[index].exact().denom() => {
+            (this.value.clone(), other.value_ref().clone().scale_by(&RATIOS[index]))
+          },
+          Some(index) => (this.value.clone().scale_by(&INVERSE_RATIOS[index]), other.value_ref().clone()),
+          None if FACTOR.exact().numer() < FACTOR.exact().denom() => (this.value.clone(), measure(other.clone())),
+          None => (this.clone().base().value(), other.clone().base().value()),
+        }
+      }
+
+      #[cfg(test)]
+      quickcheck! {
+        fn convert_matches_scale(value: Kilo<Measured>) -> bool {
+          value.clone().convert::<$name<Measured>>() == $name::<Measured>::scale(value)
+        }
+        fn convert_and_back_is_equal(value: $name<Measured>) -> bool {
+          value.clone().convert::<Milli<Measured>>().convert::<$name<Measured>>() == value
         }
       }
 
       #[cfg(test)]
       quickcheck! {
-        fn ensure_factor_calculated_correctly(value: Meter) -> bool {
-          let amount = value.clone().value() / $name::<Meter>::factor();
-          let prefixed = $name::<Meter>::from(amount);
+        fn ensure_factor_calculated_correctly(value: Measured) -> bool {
+          let amount = value.clone().value() / $name::<Measured>::factor();
+          let prefixed = $name::<Measured>::from(amount);
           prefixed.base() == value
         }
         // This is mostly done just to test that convert works. All implementations of convert should similarly work.
-        fn scale_from_kilo_works(value: Kilo<Meter>) -> bool {
-          let expected_amount = value.clone().base().value() / $name::<Meter>::factor();
+        fn scale_from_kilo_works(value: Kilo<Measured>) -> bool {
+          let expected_amount = value.clone().base().value() / $name::<Measured>::factor();
           $name::scale(value).value() == expected_amount
         }
       }
 
-      impl<B> Length for $name<B> where B: Base + Length {}
-      impl<B> Mass for $name<B> where B: Base + Mass {}
-      impl<B> Time for $name<B> where B: Base + Time {}
-      impl<B> Current for $name<B> where B: Base + Current {}
-      impl<B> Temperature for $name<B> where B: Base + Temperature{}
-      impl<B> Amount for $name<B> where B: Base + Amount {}
-      impl<B> Intensity for $name<B> where B: Base + Intensity {}
+      impl<B> IntoQuantity for $name<B> where B: $bound {
+        type Dimension = B::Dimension;
+        fn quantity(self) -> Quantity<B::Dimension, B::Storage> {
+          self.base().quantity()
+        }
+        fn quantity_kind() -> QuantityKind {
+          B::quantity_kind()
+        }
+      }
+
+      #[cfg(test)]
+      quickcheck! {
+        fn quantity_is_in_base_units(value: $name<Measured>) -> bool {
+          value.clone().quantity().value() == value.base().value()
+        }
+      }
+
+      prefix_mass! { $name, $bound }
+
+      impl<B> Length for $name<B> where B: $bound + Length {}
+      impl<B> Mass for $name<B> where B: $bound + Mass {}
+      impl<B> Time for $name<B> where B: $bound + Time {}
+      impl<B> Current for $name<B> where B: $bound + Current {}
+      impl<B> Temperature for $name<B> where B: $bound + Temperature{}
+      impl<B> Amount for $name<B> where B: $bound + Amount {}
+      impl<B> Intensity for $name<B> where B: $bound + Intensity {}
+      impl<B> Information for $name<B> where B: $bound + Information {}
 
       //
       // Conversions
       //
 
       // Create a prefix from a given base.
-      impl<B> From<B> for $name<B> where B: Base {
+      impl<B> From<B> for $name<B> where B: $bound {
         fn from(value: B) -> Self {
-          Self::new(value.value() / Self::factor())
+          Self::new(value.value().scale_by(&INVERSE_FACTOR))
         }
       }
 
       #[cfg(test)]
       quickcheck! {
-        fn into_prefix_and_back_to_base_is_equal(value: Meter) -> bool {
-          let prefixed = $name::<Meter>::from(value.clone());
+        fn into_prefix_and_back_to_base_is_equal(value: Measured) -> bool {
+          let prefixed = $name::<Measured>::from(value.clone());
           prefixed.base() == value
         }
       }
 
       // Create a prefix from a BigInt.
-      impl<B> From<BigInt> for $name<B> where B: Base {
+      impl<B> From<BigInt> for $name<B> where B: $bound {
         fn from(value: BigInt) -> Self {
           let fraction = BigRational::from_integer(value);
-          Self::new(fraction)
+          Self::from(fraction)
         }
       }
 
@@ -198,16 +423,16 @@ macro_rules! generate_prefix {
         // Unfortunately BigInt doesn't implement Arbitrary
         fn from_bigint_returns_provided_value(value: i64) -> bool {
           let bigint = BigInt::from(value);
-          let prefixed = $name::<Meter>::from(bigint.clone());
+          let prefixed = $name::<Measured>::from(bigint.clone());
           let expected_rational = BigRational::from_integer(bigint);
           prefixed.value() == expected_rational
         }
       }
 
       // Create a prefix from a BigRational.
-      impl<B> From<BigRational> for $name<B> where B: Base {
+      impl<B> From<BigRational> for $name<B> where B: $bound {
         fn from(value: BigRational) -> Self {
-          Self::new(value)
+          Self::new(B::Storage::from_rational(&value))
         }
       }
 
@@ -217,112 +442,378 @@ macro_rules! generate_prefix {
         fn from_rational_returns_provided_value(numerator: i64, denominator: i64) -> bool {
           let denominator = if denominator == 0 { 1 } else { denominator }; // The denominator cannot be zero.
           let rational = BigRational::new(BigInt::from(numerator), BigInt::from(denominator));
-          let prefixed = $name::<Meter>::from(rational.clone());
+          let prefixed = $name::<Measured>::from(rational.clone());
           prefixed.value() == rational
         }
       }
 
-      prefix_from_primitives! { $name, [i64, u64, i32, u32, i16, u16, i8, u8, isize, usize,] }
+      prefix_from_primitives! { $name, $bound, [i64, u64, i32, u32, i16, u16, i8, u8, isize, usize,] }
+      prefix_from_floats! { $name, $bound, [f64, f32,] }
+      prefix_into_integers! { $name, $bound, [i64, u64, i32, u32, i16, u16, i8, u8, i128, u128, isize, usize,] }
 
       //
       // Operations on prefixes
       //
-      impl<P,B> Add<P> for $name<B> where P: IntoBase<B>, B: Base {
+      impl<P,B> Add<P> for $name<B> where P: IntoBase<B>, B: $bound {
         type Output = Self;
         fn add(self, value: P) -> Self {
-          Self::from(self.base() + value.base())
+          Self::new(self.value + measure(value))
         }
       }
 
       #[cfg(test)]
       quickcheck! {
-        fn can_add_self(first: $name<Meter>, second: $name<Meter>) -> bool {
+        fn can_add_self(first: $name<Measured>, second: $name<Measured>) -> bool {
           let check = first.clone().base().value() + second.clone().base().value();
           (first + second).base().value() == check
         }
-        fn can_add_other(first: $name<Meter>, second: Kilo<Meter>) -> bool {
+        fn can_add_other(first: $name<Measured>, second: Kilo<Measured>) -> bool {
           let check = first.clone().base().value() + second.clone().base().value();
           (first + second).base().value() == check
         }
-        fn can_add_base(first: $name<Meter>, second: Meter) -> bool {
+        fn can_add_base(first: $name<Measured>, second: Measured) -> bool {
           let check = first.clone().base().value() + second.clone().value();
           (first + second).base().value() == check
         }
       }
 
-      impl<P,B> Sub<P> for $name<B> where P: IntoBase<B>, B: Base {
+      impl<P,B> Sub<P> for $name<B> where P: IntoBase<B>, B: $bound {
         type Output = Self;
         fn sub(self, value: P) -> Self {
-          Self::from(self.base() - value.base())
+          Self::new(self.value - measure(value))
         }
       }
 
       #[cfg(test)]
       quickcheck! {
-        fn can_sub_self(first: $name<Meter>, second: $name<Meter>) -> bool {
+        fn can_sub_self(first: $name<Measured>, second: $name<Measured>) -> bool {
           let check = first.clone().base().value() - second.clone().base().value();
           (first - second).base().value() == check
         }
-        fn can_sub_other(first: $name<Meter>, second: Kilo<Meter>) -> bool {
+        fn can_sub_other(first: $name<Measured>, second: Kilo<Measured>) -> bool {
           let check = first.clone().base().value() - second.clone().base().value();
           (first - second).base().value() == check
         }
-        fn can_sub_base(first: $name<Meter>, second: Meter) -> bool {
+        fn can_sub_base(first: $name<Measured>, second: Measured) -> bool {
           let check = first.clone().base().value() - second.clone().value();
           (first - second).base().value() == check
         }
       }
 
+      //
+      // Negation and compound assignment
+      //
+      impl<B> Neg for $name<B> where B: $bound, B::Storage: Neg<Output=B::Storage> {
+        type Output = Self;
+        fn neg(self) -> Self {
+          Self::new(-self.value)
+        }
+      }
+
+      impl<B,R> AddAssign<R> for $name<B> where B: $bound, $name<B>: Add<R, Output=$name<B>> {
+        fn add_assign(&mut self, other: R) {
+          *self = Self::new(take(&mut self.value)) + other;
+        }
+      }
+
+      impl<B,R> SubAssign<R> for $name<B> where B: $bound, $name<B>: Sub<R, Output=$name<B>> {
+        fn sub_assign(&mut self, other: R) {
+          *self = Self::new(take(&mut self.value)) - other;
+        }
+      }
+
+      impl<B,R> MulAssign<R> for $name<B> where B: $bound, $name<B>: Mul<R, Output=$name<B>> {
+        fn mul_assign(&mut self, other: R) {
+          *self = Self::new(take(&mut self.value)) * other;
+        }
+      }
+
+      impl<B,R> DivAssign<R> for $name<B> where B: $bound, $name<B>: Div<R, Output=$name<B>> {
+        fn div_assign(&mut self, other: R) {
+          *self = Self::new(take(&mut self.value)) / other;
+        }
+      }
+
+      #[cfg(test)]
+      quickcheck! {
+        fn can_neg(value: $name<Measured>) -> bool {
+          (-value.clone()).value() == -value.value()
+        }
+        fn can_assign(first: $name<Measured>, second: Kilo<Measured>) -> bool {
+          let mut value = first.clone();
+          value += second.clone();
+          value -= &first;
+          value *= 4;
+          value /= 2;
+          value == second * 2
+        }
+      }
+
+      //
+      // Operations on references, as on values
+      //
+      impl<'b,B> Add<&'b $name<B>> for $name<B> where B: $bound {
+        type Output = Self;
+        fn add(self, other: &'b $name<B>) -> Self {
+          Self::new(self.value + other.value.clone())
+        }
+      }
+
+      impl<'b,B> Sub<&'b $name<B>> for $name<B> where B: $bound {
+        type Output = Self;
+        fn sub(self, other: &'b $name<B>) -> Self {
+          Self::new(self.value - other.value.clone())
+        }
+      }
+
+      impl<'a,B> Neg for &'a $name<B> where B: $bound, B::Storage: Neg<Output=B::Storage> {
+        type Output = $name<B>;
+        fn neg(self) -> $name<B> {
+          -self.clone()
+        }
+      }
+
+      impl<'a,B,R> Add<R> for &'a $name<B> where B: $bound, $name<B>: Add<R> {
+        type Output = <$name<B> as Add<R>>::Output;
+        fn add(self, other: R) -> Self::Output {
+          self.clone() + other
+        }
+      }
+
+      impl<'a,B,R> Sub<R> for &'a $name<B> where B: $bound, $name<B>: Sub<R> {
+        type Output = <$name<B> as Sub<R>>::Output;
+        fn sub(self, other: R) -> Self::Output {
+          self.clone() - other
+        }
+      }
+
+      impl<'a,B,R> Mul<R> for &'a $name<B> where B: $bound, $name<B>: Mul<R> {
+        type Output = <$name<B> as Mul<R>>::Output;
+        fn mul(self, other: R) -> Self::Output {
+          self.clone() * other
+        }
+      }
+
+      impl<'a,B,R> Div<R> for &'a $name<B> where B: $bound, $name<B>: Div<R> {
+        type Output = <$name<B> as Div<R>>::Output;
+        fn div(self, other: R) -> Self::Output {
+          self.clone() / other
+        }
+      }
+
+      //
+      // Sums
+      //
+      impl<B> Sum for $name<B> where B: $bound {
+        fn sum<I>(iter: I) -> Self where I: Iterator<Item=Self> {
+          iter.fold(Self::new(zero()), Add::add)
+        }
+      }
+
+      impl<'a,B> Sum<&'a $name<B>> for $name<B> where B: $bound + 'a {
+        fn sum<I>(iter: I) -> Self where I: Iterator<Item=&'a $name<B>> {
+          iter.fold(Self::new(zero()), Add::add)
+        }
+      }
+
+      #[cfg(test)]
+      quickcheck! {
+        fn can_operate_on_references(first: $name<Measured>, second: $name<Measured>) -> bool {
+          let sum = &first + &second;
+          sum == first.clone() + second.clone() && &first - &second == -(&second - &first) &&
+            &first * 3 == 3 * first.clone() && (&first / Second::from(2)).value() == (first / Second::from(2)).value()
+        }
+        fn can_sum(values: Vec<i64>) -> bool {
+          let check = $name::<Measured>::from(values.iter().map(|&value| BigInt::from(value)).sum::<BigInt>());
+          let values: Vec<$name<Measured>> = values.into_iter().map($name::<Measured>::from).collect();
+          values.iter().sum::<$name<Measured>>() == check && values.into_iter().sum::<$name<Measured>>() == check
+        }
+      }
+
+      //
+      // Operations on any dimension
+      //
+      impl<U,B> Div<U> for $name<B> where U: IntoQuantity, B: $bound, Quantity<B::Dimension, B::Storage>: Div<U> {
+        type Output = <Quantity<B::Dimension, B::Storage> as Div<U>>::Output;
+        fn div(self, other: U) -> Self::Output {
+          self.quantity() / other
+        }
+      }
+
+      #[cfg(test)]
+      quickcheck! {
+        fn can_div_any(first: $name<Measured>, second: Kilo<Measured>) -> bool {
+          let check = first.clone().base().value() / second.clone().base().value();
+          (first / second).value() == check
+        }
+      }
+
+      impl<U,B> Mul<U> for $name<B> where U: IntoQuantity, B: $bound, Quantity<B::Dimension, B::Storage>: Mul<U> {
+        type Output = <Quantity<B::Dimension, B::Storage> as Mul<U>>::Output;
+        fn mul(self, other: U) -> Self::Output {
+          self.quantity() * other
+        }
+      }
+
+      #[cfg(test)]
+      quickcheck! {
+        fn can_mul_any(first: $name<Measured>, second: Measured) -> bool {
+          let check = first.clone().base().value() * second.clone().value();
+          (first * second).value() == check
+        }
+      }
+
       //
       // Dividing and multiplication are defined on integral types.
       //
-      impl<B> Div<BigRational> for $name<B> where B: Base {
+      impl<B> Div<BigRational> for $name<B> where B: $bound {
         type Output = Self;
         fn div(self, value: BigRational) -> Self {
-          Self::from(self.value() / value)
+          Self::new(self.value.scale(&value.recip()))
         }
       }
 
-      impl<B> Div<BigInt> for $name<B> where B: Base {
+      impl<B> Div<BigInt> for $name<B> where B: $bound {
         type Output = Self;
         fn div(self, value: BigInt) -> Self {
-          Self::from(self.value() / BigRational::from_integer(value))
+          self / BigRational::from_integer(value)
         }
       }
 
-      impl<B> Mul<BigRational> for $name<B> where B: Base {
+      impl<B> Mul<BigRational> for $name<B> where B: $bound {
         type Output = Self;
         fn mul(self, value: BigRational) -> Self {
-          Self::from(self.value() * value)
+          Self::new(self.value.scale(&value))
         }
       }
 
-      impl<B> Mul<BigInt> for $name<B> where B: Base {
+      impl<B> Mul<BigInt> for $name<B> where B: $bound {
         type Output = Self;
         fn mul(self, value: BigInt) -> Self {
-          Self::from(self.value() * BigRational::from_integer(value))
+          self * BigRational::from_integer(value)
+        }
+      }
+
+      prefix_div_and_mul_with_primitives! { $name, $bound, [i64, u64, i32, u32, i16, u16, i8, u8, isize, usize,] }
+      prefix_div_and_mul_with_storage! { $name, $bound, [f64, f32, ::num::rational::Ratio<i64>,] }
+
+      // Scalars multiply on either side.
+      prefix_scalar_mul! { $name, $bound, [BigRational, BigInt, i64, u64, i32, u32, i16, u16, i8, u8, isize, usize,] }
+
+      //
+      // Formatting
+      //
+      impl<B> fmt::Display for $name<B> where B: $bound {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+          format::display(self, f)
+        }
+      }
+
+      impl<B> fmt::LowerExp for $name<B> where B: $bound {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+          format::scientific(self, f)
+        }
+      }
+
+      impl<B> fmt::UpperExp for $name<B> where B: $bound {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+          format::upper_scientific(self, f)
+        }
+      }
+
+      #[test]
+      fn can_display() {
+        let value = $name::<Measured>::from(3) / 2;
+        assert_eq!(format!("{}", value), format!("1.5 {}", $name::<Measured>::shortform()));
+        assert_eq!(format!("{:#}", value), format!("1.5 {}", format::plural(&$name::<Measured>::longform())));
+        assert_eq!(format!("{:.3e}", value * 1000), format!("1.500e3 {}", $name::<Measured>::shortform()));
+      }
+
+      //
+      // Serialization
+      //
+      #[cfg(feature = "serde")]
+      impl<B> ::serde::Serialize for $name<B> where B: $bound {
+        fn serialize<Z>(&self, serializer: Z) -> Result<Z::Ok, Z::Error> where Z: ::serde::Serializer {
+          ::serialization::exact::serialize(self, serializer)
+        }
+      }
+
+      #[cfg(feature = "serde")]
+      impl<'de, B> ::serde::Deserialize<'de> for $name<B> where B: $bound {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: ::serde::Deserializer<'de> {
+          ::serialization::exact::deserialize(deserializer)
+        }
+      }
+
+      impl<B> FromStr for $name<B> where B: $bound {
+        type Err = ParseError;
+        fn from_str(input: &str) -> Result<Self, ParseError> {
+          parse::parse::<Self, B>(input)
         }
       }
 
-      prefix_div_and_mul_with_primitives! { $name, [i64, u64, i32, u32, i16, u16, i8, u8, isize, usize,] }
+      #[cfg(test)]
+      quickcheck! {
+        fn can_parse_display(value: $name<Measured>) -> bool {
+          value.to_string().parse::<$name<Measured>>() == Ok(value)
+        }
+        fn can_parse_other_prefix(value: Kilo<Measured>) -> bool {
+          value.to_string().parse::<$name<Measured>>() == Ok($name::scale(value))
+        }
+      }
 
-      impl<P,B> PartialEq<P> for $name<B> where P: IntoBase<B>, B: Base {
+      impl<P,B> PartialEq<P> for $name<B> where P: IntoBase<B>, B: $bound {
         fn eq(&self, other: &P) -> bool {
-          self.clone().base().value() == other.clone().base().value()
+          let (this, other) = in_common(self, other);
+          this == other
         }
       }
 
+      impl<B> Eq for $name<B> where B: $bound, B::Storage: Eq {}
+
       #[cfg(test)]
       quickcheck! {
-        fn can_eq_self(value: $name<Meter>) -> bool {
+        fn can_eq_self(value: $name<Measured>) -> bool {
           let duplicate = value.clone();
           value == duplicate
         }
-        fn can_eq_other(value: $name<Meter>) -> bool {
+        fn can_eq_other(value: $name<Measured>) -> bool {
           let as_kilo = Kilo::scale(value.clone());
           value == as_kilo
         }
       }
+
+      //
+      // Ordering
+      //
+      impl<P,B> PartialOrd<P> for $name<B> where P: IntoBase<B>, B: $bound {
+        fn partial_cmp(&self, other: &P) -> Option<Ordering> {
+          let (this, other) = in_common(self, other);
+          this.partial_cmp(&other)
+        }
+      }
+
+      impl<B> Ord for $name<B> where B: $bound, B::Storage: Ord {
+        fn cmp(&self, other: &Self) -> Ordering {
+          self.value_ref().cmp(other.value_ref())
+        }
+      }
+
+      #[cfg(test)]
+      quickcheck! {
+        fn can_order_self(first: $name<Measured>, second: $name<Measured>) -> bool {
+          first.cmp(&second) == first.clone().base().cmp(&second.clone().base())
+        }
+        fn can_order_other(first: $name<Measured>, second: Kilo<Measured>) -> bool {
+          let check = first.clone().base().partial_cmp(&second.clone().base());
+          first.partial_cmp(&second) == check
+        }
+        fn can_order_base(first: $name<Measured>, second: Measured) -> bool {
+          let check = first.clone().base().partial_cmp(&second);
+          first.partial_cmp(&second) == check
+        }
+      }
     }
     pub use self::$longform::$name;
   )
@@ -3,19 +3,76 @@ use num::rational::BigRational;
 use num::pow::pow;
 use num::bigint::Sign::*;
 use num::bigint::BigInt;
-use base::Base;
+use dimension::Information;
+use quantity::IntoQuantity;
 use {Unit, IntoBase};
+use storage::{Factor, Storage};
 
 #[macro_use] mod macros;
+mod kind;
+mod normalize;
 
-pub trait Prefix<B>: Unit + IntoBase<B> where B: Base {
-  /// The factor amount. Eg Kilo is 1*10^3.
+pub use self::kind::PrefixKind;
+pub use self::normalize::{normalize, Normalization, Prefixed};
+
+/// A unit that takes prefixes, such as the meter or the newton. Prefixed units are not themselves
+/// prefixable, so prefixes do not stack.
+///
+/// ```compile_fail
+/// # extern crate si;
+/// # use si::base::*;
+/// # use si::prefix::*;
+/// # fn main() {
+/// let _ = Kilo::<Kilo<Meter>>::from(1);
+/// # }
+/// ```
+pub trait Prefixable: Unit + IntoQuantity {
+  /// Whether the unit takes the binary prefixes, Kibi to Yobi, as units of information do.
+  fn takes_binary_prefixes() -> bool {
+    false
+  }
+}
+
+/// A unit that takes the binary prefixes, which are only for units of information.
+///
+/// ```compile_fail
+/// # extern crate si;
+/// # use si::base::*;
+/// # use si::prefix::*;
+/// # fn main() {
+/// let _ = Kibi::<Meter>::from(1);
+/// # }
+/// ```
+pub trait BinaryPrefixable: Prefixable + Information {}
+
+impl<B> BinaryPrefixable for B where B: Prefixable + Information {}
+
+pub trait Prefix<B>: Unit + IntoBase<B> where B: Prefixable {
+  /// The factor amount. Eg Kilo is 1*10^3 and Kibi is 2^10.
   fn factor() -> &'static BigRational;
+  /// The prefix as a value, eg `PrefixKind::Kilo`.
+  fn kind() -> PrefixKind;
+  /// The ratio of the factor of another prefix to this one, precomputed for every pair. Eg `10^6` from Kilo
+  /// into Milli.
+  fn ratio<P>() -> &'static Factor where P: Prefix<B>;
   /// Scale to a prefix.
   fn scale<P>(value: P) -> Self where P: IntoBase<B>;
+  /// Convert directly into another prefix of the same base, eg `Kilo<Meter>` into `Milli<Meter>`, scaling
+  /// once by the ratio of their factors rather than through the base.
+  fn convert<P>(self) -> P where P: Prefix<B> {
+    P::new(Storage::scale_by(self.value(), P::ratio::<Self>()))
+  }
 }
 
-fn generate_prefix_factor(exp: isize) -> BigRational {
+// Whether the prefix is below one, eg milli, so that a unit and the prefix are measured in the prefix, where
+// integer storage keeps every digit of both.
+#[doc(hidden)]
+pub fn is_fraction<B,P>() -> bool where B: Prefixable, P: Prefix<B> {
+  P::factor().numer() < P::factor().denom()
+}
+
+#[doc(hidden)]
+pub fn generate_prefix_factor(exp: isize) -> BigRational {
   let ten = BigInt::new(Plus, vec![10]);
   let one = 1.into();
   if exp >= 0 {
@@ -28,6 +85,27 @@ fn generate_prefix_factor(exp: isize) -> BigRational {
   }
 }
 
+// The factor of a binary prefix, 2^exp.
+pub(crate) fn generate_binary_prefix_factor(exp: usize) -> BigRational {
+  BigRational::from_integer(pow(BigInt::from(2), exp))
+}
+
+generate_prefix! {
+  name      = Quetta,
+  longform  = quetta,
+  shortform = Q,
+  factor    = 30,
+  doc       = "A quetta is 10^30 of the base unit.",
+}
+
+generate_prefix! {
+  name      = Ronna,
+  longform  = ronna,
+  shortform = R,
+  factor    = 27,
+  doc       = "A ronna is 10^27 of the base unit.",
+}
+
 generate_prefix! {
   name      = Yotta,
   longform  = yotta,
@@ -186,4 +264,88 @@ generate_prefix! {
   shortform = y,
   factor    = -24,
   doc       = "A yocto is 10^-24 of the base unit.", 
-}
\ No newline at end of file
+}
+
+generate_prefix! {
+  name      = Ronto,
+  longform  = ronto,
+  shortform = r,
+  factor    = -27,
+  doc       = "A ronto is 10^-27 of the base unit.",
+}
+
+generate_prefix! {
+  name      = Quecto,
+  longform  = quecto,
+  shortform = q,
+  factor    = -30,
+  doc       = "A quecto is 10^-30 of the base unit.",
+}
+
+//
+// Binary prefixes, defined by the IEC for amounts of information such as `Kibi<Byte>`.
+//
+
+generate_prefix! {
+  name      = Kibi,
+  longform  = kibi,
+  shortform = Ki,
+  binary    = 10,
+  doc       = "A kibi is 2^10 of the base unit.",
+}
+
+generate_prefix! {
+  name      = Mebi,
+  longform  = mebi,
+  shortform = Mi,
+  binary    = 20,
+  doc       = "A mebi is 2^20 of the base unit.",
+}
+
+generate_prefix! {
+  name      = Gibi,
+  longform  = gibi,
+  shortform = Gi,
+  binary    = 30,
+  doc       = "A gibi is 2^30 of the base unit.",
+}
+
+generate_prefix! {
+  name      = Tebi,
+  longform  = tebi,
+  shortform = Ti,
+  binary    = 40,
+  doc       = "A tebi is 2^40 of the base unit.",
+}
+
+generate_prefix! {
+  name      = Pebi,
+  longform  = pebi,
+  shortform = Pi,
+  binary    = 50,
+  doc       = "A pebi is 2^50 of the base unit.",
+}
+
+generate_prefix! {
+  name      = Exbi,
+  longform  = exbi,
+  shortform = Ei,
+  binary    = 60,
+  doc       = "An exbi is 2^60 of the base unit.",
+}
+
+generate_prefix! {
+  name      = Zebi,
+  longform  = zebi,
+  shortform = Zi,
+  binary    = 70,
+  doc       = "A zebi is 2^70 of the base unit.",
+}
+
+generate_prefix! {
+  name      = Yobi,
+  longform  = yobi,
+  shortform = Yi,
+  binary    = 80,
+  doc       = "A yobi is 2^80 of the base unit.",
+}
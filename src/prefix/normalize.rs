@@ -0,0 +1,182 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::marker::PhantomData;
+use num::{One, Signed, Zero};
+use num::rational::BigRational;
+use format;
+use storage::Storage;
+use IntoBase;
+use super::{PrefixKind, Prefixable};
+#[cfg(test)] use base::*;
+#[cfg(test)] use super::*;
+
+/// The prefixes `normalize` chooses from. The base unit itself is always a candidate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Normalization {
+  /// Every SI prefix, so 500 m is `5 hm`.
+  All,
+  /// Only the prefixes that are powers of 1000, leaving out hecto, deca, deci and centi, so 500 m is
+  /// `500 m`.
+  Engineering,
+  /// Only the listed prefixes and the base unit, eg millimeters, meters and kilometers for `Milli` and `Kilo`.
+  Preferred(Vec<PrefixKind>),
+}
+
+impl Normalization {
+  // The prefixes to choose from.
+  fn candidates(&self) -> Vec<PrefixKind> {
+    match *self {
+      Normalization::All => PrefixKind::all().iter().cloned().filter(|kind| !kind.is_binary()).collect(),
+      Normalization::Engineering => PrefixKind::all().iter().cloned()
+        .filter(|kind| !kind.is_binary() && !matches!(*kind, PrefixKind::Hecto | PrefixKind::Deca | PrefixKind::Deci | PrefixKind::Centi))
+        .collect(),
+      Normalization::Preferred(ref kinds) => kinds.clone(),
+    }
+  }
+}
+
+/// A value in whichever prefix `normalize` chose for it, eg `1.234567 Mm`. It formats as that prefix of the
+/// base, and compares with any unit of the base.
+#[derive(Clone, Debug)]
+pub struct Prefixed<B> where B: Prefixable {
+  value: B::Storage,
+  prefix: Option<PrefixKind>,
+  base: PhantomData<B>,
+}
+
+/// Choose the prefix that puts the value in `[1, 1000)`, or as near as the prefixes allow. Eg
+/// `Meter::from(1_234_567)` becomes `1.234567 Mm` and a zero stays in the base unit. Binary prefixes are only
+/// chosen for units of information.
+pub fn normalize<U,B>(value: U, prefixes: &Normalization) -> Prefixed<B> where U: IntoBase<B>, B: Prefixable {
+  let base = value.base();
+  let magnitude = match base.value_ref().to_rational() {
+    Some(ref value) if !value.is_zero() => value.abs(),
+    _ => return Prefixed::new(base.value(), None),
+  };
+  let mut candidates: Vec<_> = prefixes.candidates().into_iter()
+    .filter(|kind| B::takes_binary_prefixes() || !kind.is_binary())
+    .map(|kind| (Some(kind), kind.factor().clone())).collect();
+  candidates.push((None, BigRational::one()));
+  candidates.sort_by(|a, b| b.1.cmp(&a.1));
+  // The largest prefix no greater than the value, or the smallest if every one is greater.
+  let (prefix, factor) = candidates.iter().find(|candidate| candidate.1 <= magnitude)
+    .or_else(|| candidates.last())
+    .cloned()
+    .expect("the base unit is a candidate");
+  Prefixed::new(base.value().scale(&factor.recip()), prefix)
+}
+
+impl<B> Prefixed<B> where B: Prefixable {
+  fn new(value: B::Storage, prefix: Option<PrefixKind>) -> Self {
+    Prefixed {
+      value,
+      prefix,
+      base: PhantomData,
+    }
+  }
+  /// Get the value, measured in the prefix.
+  pub fn value(self) -> B::Storage {
+    self.value
+  }
+  /// Get a reference to the value, measured in the prefix.
+  pub fn value_ref(&self) -> &B::Storage {
+    &self.value
+  }
+  /// The chosen prefix, or none for the base unit.
+  pub fn prefix(&self) -> Option<PrefixKind> {
+    self.prefix
+  }
+  /// The short hand for the prefixed unit, eg `Mm`.
+  pub fn shortform(&self) -> String {
+    format!("{}{}", self.prefix.map_or("", PrefixKind::symbol), B::shortform())
+  }
+  /// The full string for the prefixed unit, eg `megameter`.
+  pub fn longform(&self) -> String {
+    format!("{}{}", self.prefix.map_or("", PrefixKind::name), B::longform())
+  }
+  /// Get the base unit.
+  pub fn base(self) -> B {
+    match self.prefix {
+      Some(kind) => B::new(self.value.scale(kind.factor())),
+      None => B::new(self.value),
+    }
+  }
+}
+
+//
+// Formatting
+//
+impl<B> fmt::Display for Prefixed<B> where B: Prefixable {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    format::write_value(&self.value, self.shortform(), self.longform(), f, format::number)
+  }
+}
+
+impl<B> fmt::LowerExp for Prefixed<B> where B: Prefixable {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    format::write_value(&self.value, self.shortform(), self.longform(), f, format::scientific_number)
+  }
+}
+
+impl<B> fmt::UpperExp for Prefixed<B> where B: Prefixable {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    format::write_value(&self.value, self.shortform(), self.longform(), f, format::upper_scientific_number)
+  }
+}
+
+//
+// Equals and ordering, in the base unit
+//
+impl<B> PartialEq for Prefixed<B> where B: Prefixable {
+  fn eq(&self, other: &Self) -> bool {
+    self.clone().base().value() == other.clone().base().value()
+  }
+}
+
+impl<P,B> PartialEq<P> for Prefixed<B> where P: IntoBase<B>, B: Prefixable {
+  fn eq(&self, other: &P) -> bool {
+    self.clone().base().value() == other.clone().base().value()
+  }
+}
+
+impl<B> PartialOrd for Prefixed<B> where B: Prefixable {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    self.clone().base().value().partial_cmp(&other.clone().base().value())
+  }
+}
+
+impl<P,B> PartialOrd<P> for Prefixed<B> where P: IntoBase<B>, B: Prefixable {
+  fn partial_cmp(&self, other: &P) -> Option<Ordering> {
+    self.clone().base().value().partial_cmp(&other.clone().base().value())
+  }
+}
+
+#[test]
+fn puts_the_value_between_one_and_a_thousand() {
+  let normal = |value: Meter| format!("{}", normalize(value, &Normalization::Engineering));
+  assert_eq!(normal(Meter::from(1_234_567)), "1.234567 Mm");
+  assert_eq!(normal(Meter::from(999)), "999 m");
+  assert_eq!(normal(Meter::from(-1_000)), "-1 km");
+  assert_eq!(normal(Meter::from(1) / 500), "2 mm");
+  assert_eq!(normal(Meter::from(0)), "0 m");
+  assert_eq!(format!("{:#}", normalize(Kilo::<Meter>::from(3) / 2_000, &Normalization::Engineering)), "1.5 meters");
+}
+
+#[test]
+fn chooses_from_the_prefixes() {
+  assert_eq!(normalize(Meter::from(500), &Normalization::All).prefix(), Some(PrefixKind::Hecto));
+  assert_eq!(normalize(Meter::from(500), &Normalization::Engineering).prefix(), None);
+  let preferred = Normalization::Preferred(vec![PrefixKind::Milli, PrefixKind::Kilo]);
+  assert_eq!(format!("{}", normalize(Meter::from(5), &preferred)), "5 m");
+  assert_eq!(format!("{}", normalize(Meter::from(5_000_000), &preferred)), "5000 km");
+  assert_eq!(format!("{}", normalize(Micro::<Meter>::from(5), &preferred)), "0.005 mm");
+}
+
+#[test]
+fn compares_with_units() {
+  let normal = normalize(Meter::from(1_500), &Normalization::Engineering);
+  assert_eq!(normal, Kilo::<Meter>::from(3) / 2);
+  assert_eq!(normal, normalize(Milli::<Meter>::from(1_500_000), &Normalization::All));
+  assert!(normal < Meter::from(2_000));
+  assert_eq!(normal.base(), Meter::from(1_500));
+}
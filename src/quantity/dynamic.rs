@@ -0,0 +1,324 @@
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+use std::ops::*;
+use num::{One, Zero};
+use num::rational::BigRational;
+use dimension::Dimension;
+use format;
+use quantity::{IntoQuantity, QuantityKind};
+use Unit;
+#[cfg(test)] use IntoBase;
+use super::format_exponents;
+#[cfg(test)] use base::*;
+#[cfg(test)] use derived::Radian;
+#[cfg(test)] use information::{Bit, Byte};
+#[cfg(test)] use temperature::{CelsiusDifference, FahrenheitDifference, KelvinDifference};
+#[cfg(test)] use prefix::*;
+#[cfg(test)] use quantity::{Dimensionless, Velocity};
+
+/// An error from combining quantities of different dimensions or kinds, such as adding meters to seconds or
+/// bytes to radians.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DimensionError {
+  /// The exponents of the dimension that was required.
+  pub expected: [isize; 7],
+  /// The exponents of the dimension that was given.
+  pub found: [isize; 7],
+  /// The kind that was required.
+  pub expected_kind: QuantityKind,
+  /// The kind that was given.
+  pub found_kind: QuantityKind,
+}
+
+// Eg `m·s⁻¹`, `1` for the dimensionless, or the kind if it is not plain.
+fn describe(exponents: &[isize; 7], kind: QuantityKind) -> String {
+  let form = format_exponents(exponents, false);
+  match kind {
+    QuantityKind::Plain if form.is_empty() => "`1`".into(),
+    QuantityKind::Plain => format!("`{}`", form),
+    QuantityKind::Information => "information".into(),
+    QuantityKind::TemperatureDifference => "temperature difference".into(),
+  }
+}
+
+impl fmt::Display for DimensionError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let expected = describe(&self.expected, self.expected_kind);
+    write!(f, "expected a quantity of {} but found {}", expected, describe(&self.found, self.found_kind))
+  }
+}
+
+impl Error for DimensionError {}
+
+/// A quantity whose dimension is only known at runtime, such as one read from user input or a database.
+///
+/// It holds a value measured in a unit that is `scale` coherent SI units, so `1.5 km` has a value of `1.5`
+/// and a scale of `1000`. Adding, subtracting and comparing check the dimensions at runtime, while
+/// multiplying and dividing combine them. Converting to and from the statically typed units is exact.
+///
+/// It also keeps the kind of its unit, so a quantity of bytes does not convert into radians though both are
+/// dimensionless. Scaling by a plain dimensionless quantity keeps the kind, while other products and
+/// quotients are plain.
+#[derive(Clone, Debug)]
+pub struct DynQuantity {
+  value: BigRational,
+  dimension: [isize; 7],
+  kind: QuantityKind,
+  scale: BigRational,
+}
+
+// The size of a unit in coherent SI units.
+fn scale_of<U>() -> BigRational where U: IntoQuantity<Storage=BigRational> {
+  U::from(BigRational::one()).quantity().value()
+}
+
+impl DynQuantity {
+  /// Create a plain quantity of the value in a unit of `scale` coherent SI units, with the exponents of
+  /// length, mass, time, current, temperature, amount and intensity.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the scale is zero.
+  pub fn new(value: BigRational, dimension: [isize; 7], scale: BigRational) -> Self {
+    assert!(!scale.is_zero(), "the scale of a quantity must not be zero");
+    DynQuantity {
+      value,
+      dimension,
+      kind: QuantityKind::Plain,
+      scale,
+    }
+  }
+  /// The same quantity of another kind, eg information.
+  pub fn with_kind(self, kind: QuantityKind) -> Self {
+    DynQuantity { kind, ..self }
+  }
+  /// The value, measured in the unit of the scale.
+  pub fn value(&self) -> &BigRational {
+    &self.value
+  }
+  /// The exponents of the seven base dimensions, as in `Dimension::exponents()`.
+  pub fn dimension(&self) -> [isize; 7] {
+    self.dimension
+  }
+  /// What the quantity measures beyond its dimension.
+  pub fn kind(&self) -> QuantityKind {
+    self.kind
+  }
+  /// The size of the unit of the value in coherent SI units, eg `1000` for kilometers.
+  pub fn scale(&self) -> &BigRational {
+    &self.scale
+  }
+  /// The value in coherent SI units.
+  pub fn coherent(&self) -> BigRational {
+    &self.value * &self.scale
+  }
+  /// The same quantity measured in a unit of a different scale.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the scale is zero.
+  pub fn rescale(&self, scale: BigRational) -> Self {
+    assert!(!scale.is_zero(), "the scale of a quantity must not be zero");
+    DynQuantity::new(self.coherent() / &scale, self.dimension, scale).with_kind(self.kind)
+  }
+  // Whether the quantity is a plain number, which scales others without changing their kind.
+  fn is_number(&self) -> bool {
+    self.kind == QuantityKind::Plain && self.dimension == [0; 7]
+  }
+  // Check a quantity has the dimension and kind of another, reporting the other as expected.
+  fn check_against(&self, expected: [isize; 7], expected_kind: QuantityKind) -> Result<(), DimensionError> {
+    if self.dimension == expected && self.kind == expected_kind {
+      Ok(())
+    } else {
+      Err(DimensionError { expected, found: self.dimension, expected_kind, found_kind: self.kind })
+    }
+  }
+  // Check the other quantity has the same dimension and kind.
+  fn check(&self, other: &DynQuantity) -> Result<(), DimensionError> {
+    other.check_against(self.dimension, self.kind)
+  }
+  /// Add a quantity of the same dimension, keeping the scale of this one.
+  pub fn checked_add(&self, other: &DynQuantity) -> Result<Self, DimensionError> {
+    self.check(other)?;
+    Ok(DynQuantity::new(&self.value + other.coherent() / &self.scale, self.dimension, self.scale.clone()).with_kind(self.kind))
+  }
+  /// Subtract a quantity of the same dimension, keeping the scale of this one.
+  pub fn checked_sub(&self, other: &DynQuantity) -> Result<Self, DimensionError> {
+    self.check(other)?;
+    Ok(DynQuantity::new(&self.value - other.coherent() / &self.scale, self.dimension, self.scale.clone()).with_kind(self.kind))
+  }
+  /// Compare with a quantity of the same dimension.
+  pub fn checked_cmp(&self, other: &DynQuantity) -> Result<Ordering, DimensionError> {
+    self.check(other)?;
+    Ok(self.coherent().cmp(&other.coherent()))
+  }
+  /// Convert to a statically typed unit of the same dimension and kind, eg `Kilo<Meter>`.
+  pub fn to_unit<U>(&self) -> Result<U, DimensionError> where U: IntoQuantity<Storage=BigRational> {
+    self.check_against(U::Dimension::exponents(), U::quantity_kind())?;
+    Ok(U::from(self.coherent() / scale_of::<U>()))
+  }
+}
+
+impl<U> From<U> for DynQuantity where U: IntoQuantity<Storage=BigRational> {
+  fn from(unit: U) -> Self {
+    DynQuantity::new(unit.value(), U::Dimension::exponents(), scale_of::<U>()).with_kind(U::quantity_kind())
+  }
+}
+
+//
+// Operations
+//
+impl Mul for DynQuantity {
+  type Output = Self;
+  fn mul(self, other: Self) -> Self {
+    let kind = if other.is_number() { self.kind } else if self.is_number() { other.kind } else { QuantityKind::Plain };
+    let mut dimension = self.dimension;
+    for (exponent, other) in dimension.iter_mut().zip(other.dimension.iter()) {
+      *exponent += other;
+    }
+    DynQuantity::new(self.value * other.value, dimension, self.scale * other.scale).with_kind(kind)
+  }
+}
+
+impl Div for DynQuantity {
+  type Output = Self;
+  fn div(self, other: Self) -> Self {
+    let kind = if other.is_number() { self.kind } else { QuantityKind::Plain };
+    let mut dimension = self.dimension;
+    for (exponent, other) in dimension.iter_mut().zip(other.dimension.iter()) {
+      *exponent -= other;
+    }
+    DynQuantity::new(self.value / other.value, dimension, self.scale / other.scale).with_kind(kind)
+  }
+}
+
+impl Mul<BigRational> for DynQuantity {
+  type Output = Self;
+  fn mul(self, value: BigRational) -> Self {
+    DynQuantity::new(self.value * value, self.dimension, self.scale).with_kind(self.kind)
+  }
+}
+
+impl Div<BigRational> for DynQuantity {
+  type Output = Self;
+  fn div(self, value: BigRational) -> Self {
+    DynQuantity::new(self.value / value, self.dimension, self.scale).with_kind(self.kind)
+  }
+}
+
+//
+// Equals and ordering. Quantities of different dimensions or kinds are unequal and unordered.
+//
+impl PartialEq for DynQuantity {
+  fn eq(&self, other: &DynQuantity) -> bool {
+    self.check(other).is_ok() && self.coherent() == other.coherent()
+  }
+}
+
+impl Eq for DynQuantity {}
+
+impl PartialOrd for DynQuantity {
+  fn partial_cmp(&self, other: &DynQuantity) -> Option<Ordering> {
+    self.checked_cmp(other).ok()
+  }
+}
+
+//
+// Formatting
+//
+
+// Written in coherent SI units, as the unit of the scale has no name, eg `1500 m`.
+impl fmt::Display for DynQuantity {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let number = format::number(&self.coherent(), f.precision());
+    let form = format_exponents(&self.dimension, f.alternate());
+    if form.is_empty() {
+      format::pad(f, &number)
+    } else {
+      format::pad(f, &format!("{} {}", number, form))
+    }
+  }
+}
+
+#[test]
+fn keeps_the_scale_of_prefixes() {
+  let distance = DynQuantity::from(Kilo::<Meter>::from(3) / 2i64);
+  assert_eq!(distance.value(), &(BigRational::from_integer(3.into()) / BigRational::from_integer(2.into())));
+  assert_eq!(distance.scale(), &BigRational::from_integer(1_000.into()));
+  assert_eq!(distance.dimension(), [1, 0, 0, 0, 0, 0, 0]);
+}
+
+#[test]
+fn converts_losslessly() {
+  let distance: Milli<Meter> = Milli::from(1) / 3;
+  let dynamic = DynQuantity::from(distance.clone());
+  assert_eq!(dynamic.to_unit::<Milli<Meter>>(), Ok(distance.clone()));
+  assert_eq!(dynamic.to_unit::<Meter>(), Ok(distance.base()));
+  let mass = DynQuantity::from(Gram::from(1_500));
+  assert_eq!(mass.to_unit::<Kilogram>(), Ok(Kilogram::from(3) / 2));
+  let difference = DynQuantity::from(FahrenheitDifference::from(9));
+  assert_eq!(difference.to_unit::<KelvinDifference>(), Ok(KelvinDifference::from(5)));
+  assert_eq!(difference.to_unit::<FahrenheitDifference>(), Ok(FahrenheitDifference::from(9)));
+}
+
+#[test]
+fn keeps_temperature_differences_apart() {
+  let difference = DynQuantity::from(FahrenheitDifference::from(9));
+  let temperature = DynQuantity::from(Kelvin::from(5));
+  let kinds = (QuantityKind::Plain, QuantityKind::TemperatureDifference);
+  let error = DimensionError { expected: [0, 0, 0, 0, 1, 0, 0], found: [0, 0, 0, 0, 1, 0, 0], expected_kind: kinds.0, found_kind: kinds.1 };
+  assert_eq!(difference.to_unit::<Kelvin>(), Err(error.clone()));
+  assert_eq!(error.to_string(), "expected a quantity of `K` but found temperature difference");
+  assert!(temperature.to_unit::<CelsiusDifference>().is_err());
+  assert!(temperature.checked_add(&difference).is_err());
+}
+
+#[test]
+fn checks_dimensions() {
+  let distance = DynQuantity::from(Kilo::<Meter>::from(1));
+  let time = DynQuantity::from(Second::from(10));
+  let plain = QuantityKind::Plain;
+  let error = DimensionError { expected: [1, 0, 0, 0, 0, 0, 0], found: [0, 0, 1, 0, 0, 0, 0], expected_kind: plain, found_kind: plain };
+  assert_eq!(distance.checked_add(&time), Err(error.clone()));
+  assert_eq!(distance.to_unit::<Second>(), Err(DimensionError { expected: error.found, found: error.expected, ..error.clone() }));
+  assert_eq!(error.to_string(), "expected a quantity of `m` but found `s`");
+  assert_eq!(distance.partial_cmp(&time), None);
+  assert!(distance != time);
+}
+
+#[test]
+fn can_do_arithmetic() {
+  let distance = DynQuantity::from(Kilo::<Meter>::from(1));
+  let sum = distance.checked_add(&DynQuantity::from(Meter::from(500))).unwrap();
+  assert_eq!(sum.to_unit::<Meter>(), Ok(Meter::from(1_500)));
+  assert_eq!(sum.scale(), distance.scale());
+  let speed = sum / DynQuantity::from(Second::from(10));
+  assert_eq!(speed.to_unit::<Velocity>(), Ok(Velocity::from(150)));
+  assert_eq!(format!("{}", speed), "150 m·s⁻¹");
+  assert!(DynQuantity::from(Kilo::<Meter>::from(1)) > DynQuantity::from(Meter::from(999)));
+}
+
+#[test]
+fn keeps_information_apart() {
+  let size = DynQuantity::from(Kilo::<Byte>::from(1));
+  let angle = DynQuantity::from(Radian::from(8_000));
+  let zero = [0; 7];
+  let error = DimensionError { expected: zero, found: zero, expected_kind: QuantityKind::Information, found_kind: QuantityKind::Plain };
+  assert_eq!(size.kind(), QuantityKind::Information);
+  assert_eq!(angle.to_unit::<Bit>(), Err(error.clone()));
+  assert_eq!(error.to_string(), "expected a quantity of information but found `1`");
+  assert!(size.to_unit::<Radian>().is_err());
+  assert!(size.checked_add(&angle).is_err());
+  assert!(size != angle);
+  assert_eq!(size.to_unit::<Bit>(), Ok(Bit::from(8_000)));
+  let doubled = size.clone() * DynQuantity::from(Dimensionless::from(2));
+  assert_eq!(doubled.to_unit::<Kilo<Byte>>(), Ok(Kilo::<Byte>::from(2)));
+  assert_eq!((size.clone() / size).kind(), QuantityKind::Plain);
+}
+
+#[test]
+#[should_panic(expected = "the scale of a quantity must not be zero")]
+fn rejects_zero_scale() {
+  DynQuantity::from(Meter::from(1)).rescale(BigRational::zero());
+}
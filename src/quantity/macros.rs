@@ -0,0 +1,58 @@
+macro_rules! quantity_from_primitives {
+  {
+    [$($primitive:ty,)*]
+  } => {
+    $(
+      impl<D,S> From<$primitive> for Quantity<D,S> where D: Dimension, S: Storage {
+        fn from(value: $primitive) -> Self {
+          Self::new(::storage::from_integer(value))
+        }
+      }
+    )*
+  }
+}
+
+macro_rules! quantity_div_and_mul_with_primitives {
+  {
+    [$($primitive:ty,)*]
+  } => {
+    $(
+      impl<D,S> Div<$primitive> for Quantity<D,S> where D: Dimension, S: Storage {
+        type Output = Self;
+        fn div(self, value: $primitive) -> Self {
+          Self::new(self.value / ::storage::from_integer(value))
+        }
+      }
+
+      impl<D,S> Mul<$primitive> for Quantity<D,S> where D: Dimension, S: Storage {
+        type Output = Self;
+        fn mul(self, value: $primitive) -> Self {
+          Self::new(self.value * ::storage::from_integer(value))
+        }
+      }
+    )*
+  }
+}
+
+// Scalars in the storage of a quantity multiply and divide its value directly.
+macro_rules! quantity_div_and_mul_with_storage {
+  {
+    [$($storage:ty,)*]
+  } => {
+    $(
+      impl<D> Div<$storage> for Quantity<D,$storage> where D: Dimension {
+        type Output = Self;
+        fn div(self, value: $storage) -> Self {
+          Self::new(self.value / value)
+        }
+      }
+
+      impl<D> Mul<$storage> for Quantity<D,$storage> where D: Dimension {
+        type Output = Self;
+        fn mul(self, value: $storage) -> Self {
+          Self::new(self.value * value)
+        }
+      }
+    )*
+  }
+}
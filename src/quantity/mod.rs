@@ -0,0 +1,496 @@
+#[cfg(test)] use quickcheck::{Arbitrary, Gen};
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::Product;
+use format;
+use parse::{self, ParseError};
+use std::marker::PhantomData;
+use std::ops::*;
+use std::str::FromStr;
+use num::bigint::BigInt;
+use num::rational::BigRational;
+use typenum::{Prod, Quot, Z0, P1, P2, P3, P4, N1, N2, N3};
+use base::*;
+#[cfg(test)] use prefix::{Kilo, Milli};
+use dimension::{Dimension, Dim};
+use storage::Storage;
+use Unit;
+
+#[macro_use] mod macros;
+mod dynamic;
+
+pub use self::dynamic::{DynQuantity, DimensionError};
+
+/// The dimensionless quantity, such as the ratio of two lengths.
+pub type Dimensionless = Quantity<Dim<Z0, Z0, Z0, Z0, Z0, Z0, Z0>>;
+/// A quantity of length, in meters.
+pub type Length = Quantity<Dim<P1, Z0, Z0, Z0, Z0, Z0, Z0>>;
+/// A quantity of mass, in kilograms.
+pub type Mass = Quantity<Dim<Z0, P1, Z0, Z0, Z0, Z0, Z0>>;
+/// A quantity of time, in seconds.
+pub type Time = Quantity<Dim<Z0, Z0, P1, Z0, Z0, Z0, Z0>>;
+/// A quantity of electrical current, in amperes.
+pub type Current = Quantity<Dim<Z0, Z0, Z0, P1, Z0, Z0, Z0>>;
+/// A quantity of thermodynamic temperature, in kelvin.
+pub type Temperature = Quantity<Dim<Z0, Z0, Z0, Z0, P1, Z0, Z0>>;
+/// A quantity of the amount of a substance, in moles.
+pub type Amount = Quantity<Dim<Z0, Z0, Z0, Z0, Z0, P1, Z0>>;
+/// A quantity of luminous intensity, in candela.
+pub type Intensity = Quantity<Dim<Z0, Z0, Z0, Z0, Z0, Z0, P1>>;
+/// A quantity of information, in bits. Information is dimensionless, so this is `Dimensionless`, but it does
+/// not convert into bits or bytes, as other dimensionless quantities would too.
+pub type Information = Dimensionless;
+/// A quantity of area, in square meters.
+pub type Area = Quantity<Dim<P2, Z0, Z0, Z0, Z0, Z0, Z0>>;
+/// A quantity of volume, in cubic meters.
+pub type Volume = Quantity<Dim<P3, Z0, Z0, Z0, Z0, Z0, Z0>>;
+/// A quantity of velocity, in meters per second.
+pub type Velocity = Quantity<Dim<P1, Z0, N1, Z0, Z0, Z0, Z0>>;
+/// A quantity of acceleration, in meters per second squared.
+pub type Acceleration = Quantity<Dim<P1, Z0, N2, Z0, Z0, Z0, Z0>>;
+/// A quantity of plane angle, in radians.
+pub type Angle = Dimensionless;
+/// A quantity of solid angle, in steradians.
+pub type SolidAngle = Dimensionless;
+/// A quantity of frequency, in hertz.
+pub type Frequency = Quantity<Dim<Z0, Z0, N1, Z0, Z0, Z0, Z0>>;
+/// A quantity of force, in newtons.
+pub type Force = Quantity<Dim<P1, P1, N2, Z0, Z0, Z0, Z0>>;
+/// A quantity of pressure, in pascals.
+pub type Pressure = Quantity<Dim<N1, P1, N2, Z0, Z0, Z0, Z0>>;
+/// A quantity of energy, in joules.
+pub type Energy = Quantity<Dim<P2, P1, N2, Z0, Z0, Z0, Z0>>;
+/// A quantity of power, in watts.
+pub type Power = Quantity<Dim<P2, P1, N3, Z0, Z0, Z0, Z0>>;
+/// A quantity of electric charge, in coulombs.
+pub type Charge = Quantity<Dim<Z0, Z0, P1, P1, Z0, Z0, Z0>>;
+/// A quantity of electric potential difference, in volts.
+pub type Voltage = Quantity<Dim<P2, P1, N3, N1, Z0, Z0, Z0>>;
+/// A quantity of capacitance, in farads.
+pub type Capacitance = Quantity<Dim<N2, N1, P4, P2, Z0, Z0, Z0>>;
+/// A quantity of electrical resistance, in ohms.
+pub type Resistance = Quantity<Dim<P2, P1, N3, N2, Z0, Z0, Z0>>;
+/// A quantity of electrical conductance, in siemens.
+pub type Conductance = Quantity<Dim<N2, N1, P3, P2, Z0, Z0, Z0>>;
+/// A quantity of magnetic flux, in webers.
+pub type MagneticFlux = Quantity<Dim<P2, P1, N2, N1, Z0, Z0, Z0>>;
+/// A quantity of magnetic flux density, in teslas.
+pub type MagneticFluxDensity = Quantity<Dim<Z0, P1, N2, N1, Z0, Z0, Z0>>;
+/// A quantity of inductance, in henries.
+pub type Inductance = Quantity<Dim<P2, P1, N2, N2, Z0, Z0, Z0>>;
+/// A quantity of luminous flux, in lumens.
+pub type LuminousFlux = Quantity<Dim<Z0, Z0, Z0, Z0, Z0, Z0, P1>>;
+/// A quantity of illuminance, in lux.
+pub type Illuminance = Quantity<Dim<N2, Z0, Z0, Z0, Z0, Z0, P1>>;
+/// A quantity of radioactivity, in becquerels. It is the same quantity as `Frequency`.
+pub type Activity = Quantity<Dim<Z0, Z0, N1, Z0, Z0, Z0, Z0>>;
+/// A quantity of absorbed dose, in grays.
+pub type AbsorbedDose = Quantity<Dim<P2, Z0, N2, Z0, Z0, Z0, Z0>>;
+/// A quantity of equivalent dose, in sieverts. It is the same quantity as `AbsorbedDose`.
+pub type EquivalentDose = Quantity<Dim<P2, Z0, N2, Z0, Z0, Z0, Z0>>;
+/// A quantity of catalytic activity, in katals.
+pub type CatalyticActivity = Quantity<Dim<Z0, Z0, N1, Z0, Z0, P1, Z0>>;
+
+/// What a unit measures beyond its dimension. Units of the same dimension but another kind, such as the bit
+/// and the radian, do not convert into each other.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum QuantityKind {
+  /// Any quantity of the dimension, as most units measure.
+  Plain,
+  /// Information, such as bits and bytes, which is dimensionless in the SI.
+  Information,
+  /// A difference between temperatures, rather than a thermodynamic temperature such as a `Kelvin`.
+  TemperatureDifference,
+}
+
+pub trait IntoQuantity: Unit {
+  /// The dimension of the unit.
+  type Dimension: Dimension;
+  /// Get the value as a quantity in coherent SI units.
+  fn quantity(self) -> Quantity<Self::Dimension, Self::Storage>;
+  /// What the unit measures beyond its dimension, eg information for the bit.
+  fn quantity_kind() -> QuantityKind {
+    QuantityKind::Plain
+  }
+}
+
+/// A quantity is a value of any dimension expressed in coherent SI base units. Multiplying or dividing
+/// units produces a quantity of the resulting dimension, while adding quantities of different dimensions
+/// does not compile. Quantities store a `BigRational` unless another `Storage` is chosen, as when
+/// multiplying units that store an `f64`.
+///
+/// ```compile_fail
+/// # extern crate si;
+/// # use si::base::*;
+/// # fn main() {
+/// let area = Meter::from(2) * Meter::from(3);
+/// let _ = area + Meter::from(1);
+/// # }
+/// ```
+pub struct Quantity<D, S = BigRational> where D: Dimension {
+  value: S,
+  dimension: PhantomData<D>,
+}
+
+// Symbols for each base dimension, in the order of `Dimension::exponents()`.
+fn base_forms(long: bool) -> [String; 7] {
+  if long {
+    [Meter::longform(), Kilogram::longform(), Second::longform(), Ampere::longform(),
+     Kelvin::longform(), Mole::longform(), Candela::longform()]
+  } else {
+    [Meter::shortform(), Kilogram::shortform(), Second::shortform(), Ampere::shortform(),
+     Kelvin::shortform(), Mole::shortform(), Candela::shortform()]
+  }
+}
+
+fn superscript(exponent: isize) -> String {
+  exponent.to_string().chars().map(|digit| match digit {
+    '-' => '⁻',
+    '0' => '⁰',
+    '1' => '¹',
+    '2' => '²',
+    '3' => '³',
+    '4' => '⁴',
+    '5' => '⁵',
+    '6' => '⁶',
+    '7' => '⁷',
+    '8' => '⁸',
+    _   => '⁹',
+  }).collect()
+}
+
+// Eg `m·s⁻²` for acceleration. Dimensionless quantities have an empty form.
+fn format_dimension<D>(long: bool) -> String where D: Dimension {
+  format_exponents(&D::exponents(), long)
+}
+
+fn format_exponents(exponents: &[isize; 7], long: bool) -> String {
+  exponents.iter().zip(base_forms(long).iter())
+    .filter(|&(&exponent, _)| exponent != 0)
+    .map(|(&exponent, form)| match exponent {
+      1 => form.clone(),
+      _ => format!("{}{}", form, superscript(exponent)),
+    })
+    .collect::<Vec<_>>()
+    .join("·")
+}
+
+impl<D,S> Unit for Quantity<D,S> where D: Dimension, S: Storage {
+  type Storage = S;
+  fn new(value: S) -> Self {
+    Quantity {
+      value,
+      dimension: PhantomData,
+    }
+  }
+  fn shortform() -> String {
+    format_dimension::<D>(false)
+  }
+  fn longform() -> String {
+    format_dimension::<D>(true)
+  }
+  fn value(self) -> S {
+    self.value
+  }
+  fn value_ref(&self) -> &S {
+    &self.value
+  }
+}
+
+#[test]
+fn has_right_shortform() {
+  assert_eq!(Length::shortform(), "m");
+  assert_eq!(Acceleration::shortform(), "m·s⁻²");
+  assert_eq!(Force::shortform(), "m·kg·s⁻²");
+  assert_eq!(Dimensionless::shortform(), "");
+}
+
+#[test]
+fn has_right_longform() {
+  assert_eq!(Velocity::longform(), "meter·second⁻¹");
+}
+
+#[cfg(test)]
+impl<D> Arbitrary for Quantity<D> where D: Dimension + Send + 'static {
+  fn arbitrary<G: Gen>(g: &mut G) -> Self {
+    let (numerator, denominator) = (g.gen::<i64>(), g.gen::<i64>());
+    let denominator = if denominator == 0 { 1 } else { denominator }; // The denominator cannot be zero.
+
+    let rational = BigRational::new(BigInt::from(numerator), BigInt::from(denominator));
+    Self::new(rational)
+  }
+}
+
+impl<D,S> Clone for Quantity<D,S> where D: Dimension, S: Storage {
+  fn clone(&self) -> Self {
+    Self::new(self.value.clone())
+  }
+}
+
+impl<D,S> fmt::Debug for Quantity<D,S> where D: Dimension, S: Storage {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("Quantity")
+      .field("value", &self.value)
+      .field("dimension", &D::exponents())
+      .finish()
+  }
+}
+
+//
+// Markers
+//
+impl<D,S> IntoQuantity for Quantity<D,S> where D: Dimension, S: Storage {
+  type Dimension = D;
+  fn quantity(self) -> Self {
+    self
+  }
+}
+
+//
+// Conversions
+//
+impl<D,S> From<BigInt> for Quantity<D,S> where D: Dimension, S: Storage {
+  fn from(value: BigInt) -> Self {
+    Self::from(BigRational::from_integer(value))
+  }
+}
+
+impl<D,S> From<BigRational> for Quantity<D,S> where D: Dimension, S: Storage {
+  fn from(value: BigRational) -> Self {
+    Self::new(S::from_rational(&value))
+  }
+}
+
+quantity_from_primitives! { [i64, u64, i32, u32, i16, u16, i8, u8, isize, usize,] }
+
+// A dimensionless quantity is just a number.
+impl From<Dimensionless> for BigRational {
+  fn from(value: Dimensionless) -> Self {
+    value.value()
+  }
+}
+
+//
+// Operations on quantities of the same dimension
+//
+impl<D,S,U> Add<U> for Quantity<D,S> where U: IntoQuantity<Dimension=D, Storage=S>, D: Dimension, S: Storage {
+  type Output = Self;
+  fn add(self, other: U) -> Self {
+    Self::new(self.value + other.quantity().value)
+  }
+}
+
+#[cfg(test)]
+quickcheck! {
+  fn can_add_self(first: Length, second: Length) -> bool {
+    let check = first.clone().value() + second.clone().value();
+    (first + second).value() == check
+  }
+  fn can_add_base(first: Length, second: Meter) -> bool {
+    let check = first.clone().value() + second.clone().value();
+    (first + second).value() == check
+  }
+}
+
+impl<D,S,U> Sub<U> for Quantity<D,S> where U: IntoQuantity<Dimension=D, Storage=S>, D: Dimension, S: Storage {
+  type Output = Self;
+  fn sub(self, other: U) -> Self {
+    Self::new(self.value - other.quantity().value)
+  }
+}
+
+#[cfg(test)]
+quickcheck! {
+  fn can_sub_self(first: Length, second: Length) -> bool {
+    let check = first.clone().value() - second.clone().value();
+    (first - second).value() == check
+  }
+}
+
+//
+// Operations on quantities of any dimension
+//
+impl<D,S,U> Mul<U> for Quantity<D,S>
+where U: IntoQuantity<Storage=S>, D: Dimension + Mul<U::Dimension>, Prod<D, U::Dimension>: Dimension, S: Storage {
+  type Output = Quantity<Prod<D, U::Dimension>, S>;
+  fn mul(self, other: U) -> Self::Output {
+    Quantity::new(self.value * other.quantity().value)
+  }
+}
+
+#[cfg(test)]
+quickcheck! {
+  fn can_mul_other(first: Length, second: Time) -> bool {
+    let check = first.clone().value() * second.clone().value();
+    (first * second).value() == check
+  }
+}
+
+impl<D,S,U> Div<U> for Quantity<D,S>
+where U: IntoQuantity<Storage=S>, D: Dimension + Div<U::Dimension>, Quot<D, U::Dimension>: Dimension, S: Storage {
+  type Output = Quantity<Quot<D, U::Dimension>, S>;
+  fn div(self, other: U) -> Self::Output {
+    Quantity::new(self.value / other.quantity().value)
+  }
+}
+
+#[cfg(test)]
+quickcheck! {
+  fn can_div_other(first: Length, second: Time) -> bool {
+    let second = if second.value_ref() == &BigRational::from_integer(0.into()) { Time::from(1) } else { second };
+    let check = first.clone().value() / second.clone().value();
+    (first / second).value() == check
+  }
+}
+
+//
+// Products, only of dimensionless quantities, as the dimension of any other product depends on the number
+// of values.
+//
+impl<S> Product for Quantity<Dim<Z0, Z0, Z0, Z0, Z0, Z0, Z0>, S> where S: Storage {
+  fn product<I>(iter: I) -> Self where I: Iterator<Item=Self> {
+    Self::new(iter.fold(S::from_i64(1), |product, value| product * value.value))
+  }
+}
+
+impl<'a,S> Product<&'a Quantity<Dim<Z0, Z0, Z0, Z0, Z0, Z0, Z0>, S>> for Quantity<Dim<Z0, Z0, Z0, Z0, Z0, Z0, Z0>, S>
+where S: Storage + 'a {
+  fn product<I>(iter: I) -> Self where I: Iterator<Item=&'a Self> {
+    iter.cloned().product()
+  }
+}
+
+#[test]
+fn multiplies_dimensionless_quantities() {
+  let growth = vec![Dimensionless::from(3) / 2, Dimensionless::from(2), Dimensionless::from(5) / 4];
+  assert_eq!(growth.iter().product::<Dimensionless>(), Dimensionless::from(15) / 4);
+  assert_eq!(growth.into_iter().product::<Dimensionless>(), Dimensionless::from(15) / 4);
+  assert_eq!(Vec::<Dimensionless>::new().into_iter().product::<Dimensionless>(), Dimensionless::from(1));
+  let ratios = [Meter::from(3) / Meter::from(2), Second::from(4) / Milli::<Second>::from(500)];
+  assert_eq!(ratios.iter().product::<Dimensionless>(), Dimensionless::from(12));
+}
+
+//
+// Dividing and multiplication are defined on integral types.
+//
+impl<D,S> Div<BigRational> for Quantity<D,S> where D: Dimension, S: Storage {
+  type Output = Self;
+  fn div(self, value: BigRational) -> Self {
+    Self::new(self.value.scale(&value.recip()))
+  }
+}
+
+impl<D,S> Div<BigInt> for Quantity<D,S> where D: Dimension, S: Storage {
+  type Output = Self;
+  fn div(self, value: BigInt) -> Self {
+    self / BigRational::from_integer(value)
+  }
+}
+
+impl<D,S> Mul<BigRational> for Quantity<D,S> where D: Dimension, S: Storage {
+  type Output = Self;
+  fn mul(self, value: BigRational) -> Self {
+    Self::new(self.value.scale(&value))
+  }
+}
+
+impl<D,S> Mul<BigInt> for Quantity<D,S> where D: Dimension, S: Storage {
+  type Output = Self;
+  fn mul(self, value: BigInt) -> Self {
+    self * BigRational::from_integer(value)
+  }
+}
+
+quantity_div_and_mul_with_primitives! { [i64, u64, i32, u32, i16, u16, i8, u8, isize, usize,] }
+quantity_div_and_mul_with_storage! { [f64, f32, ::num::rational::Ratio<i64>,] }
+
+//
+// Formatting
+//
+impl<D,S> fmt::Display for Quantity<D,S> where D: Dimension, S: Storage {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    format::display(self, f)
+  }
+}
+
+impl<D,S> fmt::LowerExp for Quantity<D,S> where D: Dimension, S: Storage {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    format::scientific(self, f)
+  }
+}
+
+impl<D,S> fmt::UpperExp for Quantity<D,S> where D: Dimension, S: Storage {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    format::upper_scientific(self, f)
+  }
+}
+
+#[test]
+fn can_display() {
+  assert_eq!(format!("{}", Area::from(6)), "6 m²");
+  assert_eq!(format!("{:#}", Velocity::from(3) / 2), "1.5 meter·second⁻¹");
+  assert_eq!(format!("{:E}", Dimensionless::from(15_000)), "1.5E4");
+  assert_eq!(format!("{}", format::Engineering(&Dimensionless::from(15_000))), "15e3");
+}
+
+impl<D,S> FromStr for Quantity<D,S> where D: Dimension, S: Storage {
+  type Err = ParseError;
+  fn from_str(input: &str) -> Result<Self, ParseError> {
+    parse::parse_exact::<Self>(input)
+  }
+}
+
+#[test]
+fn can_parse() {
+  assert_eq!("6 m²".parse::<Area>(), Ok(Area::from(6)));
+  assert_eq!("1/2".parse::<Dimensionless>(), Ok(Dimensionless::from(1) / 2));
+  assert!("6 m".parse::<Area>().is_err());
+}
+
+//
+// Equals
+//
+impl<D,S,U> PartialEq<U> for Quantity<D,S> where U: IntoQuantity<Dimension=D, Storage=S>, D: Dimension, S: Storage {
+  fn eq(&self, other: &U) -> bool {
+    self.value == other.clone().quantity().value
+  }
+}
+
+impl<D,S> Eq for Quantity<D,S> where D: Dimension, S: Storage + Eq {}
+
+#[cfg(test)]
+quickcheck! {
+  fn can_eq_self(value: Length) -> bool {
+    let duplicate = value.clone();
+    value == duplicate
+  }
+  fn can_eq_base(value: Meter) -> bool {
+    value.clone().quantity() == value
+  }
+}
+
+//
+// Ordering
+//
+impl<D,S,U> PartialOrd<U> for Quantity<D,S> where U: IntoQuantity<Dimension=D, Storage=S>, D: Dimension, S: Storage {
+  fn partial_cmp(&self, other: &U) -> Option<Ordering> {
+    self.value.partial_cmp(&other.clone().quantity().value)
+  }
+}
+
+impl<D,S> Ord for Quantity<D,S> where D: Dimension, S: Storage + Ord {
+  fn cmp(&self, other: &Self) -> Ordering {
+    self.value.cmp(&other.value)
+  }
+}
+
+#[cfg(test)]
+quickcheck! {
+  fn can_order_self(first: Length, second: Length) -> bool {
+    first.cmp(&second) == first.value_ref().cmp(second.value_ref())
+  }
+  fn can_order_base(first: Length, second: Kilo<Meter>) -> bool {
+    let check = first.value_ref().partial_cmp(second.clone().quantity().value_ref());
+    first.partial_cmp(&second) == check
+  }
+}
@@ -0,0 +1,306 @@
+//! A registry of units by name, for reading units from other systems at runtime.
+//!
+//! Every unit of this crate, with every prefix of those that take them, is found by its shortform, longform,
+//! plural or an alias, eg `km`, `kilometer`, `kilometers` or `kilometre`. Units defined with `base_unit!`
+//! join with `register_prefixable`, and other units with `register`. Absolute temperatures such as
+//! `DegreeCelsius` are offset from the kelvin rather than a multiple of it, so only their differences are
+//! registered.
+//!
+//! A name can have more than one reading: `m` is the meter and the milli prefix, and `ft` is the foot and a
+//! femtotonne. `find` gives every reading, while `lookup` takes a unit named directly over a prefixed one and
+//! reports an ambiguity between readings of the same kind.
+use std::error::Error;
+use std::fmt;
+use std::sync::{PoisonError, RwLock};
+use num::One;
+use num::rational::BigRational;
+#[cfg(test)] use accepted::{Minute, Tonne};
+#[cfg(test)] use base::*;
+#[cfg(test)] use derived::Radian;
+#[cfg(test)] use information::Byte;
+use dimension::Dimension;
+use parse::names_forms;
+use prefix::{PrefixKind, Prefixable};
+use quantity::{DynQuantity, IntoQuantity, QuantityKind};
+use {accepted, base, customary, derived, information, temperature, Unit};
+
+/// A registered unit.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnitInfo {
+  /// The short hand, eg `m`.
+  pub shortform: String,
+  /// The full name, eg `meter`.
+  pub longform: String,
+  /// Other names, eg `metre`.
+  pub aliases: Vec<String>,
+  /// The exponents of the dimension, as in `Dimension::exponents()`.
+  pub dimension: [isize; 7],
+  /// What the unit measures beyond its dimension, eg information for the bit.
+  pub kind: QuantityKind,
+  /// The size in coherent SI units, eg 1/1000 for the gram.
+  pub factor: BigRational,
+  /// Whether the unit takes prefixes.
+  pub prefixable: bool,
+  /// Whether the unit also takes the binary prefixes, as units of information do.
+  pub binary: bool,
+}
+
+impl UnitInfo {
+  pub(crate) fn of<U>() -> Self where U: IntoQuantity<Storage=BigRational> {
+    UnitInfo {
+      shortform: U::shortform(),
+      longform: U::longform(),
+      aliases: Vec::new(),
+      dimension: U::Dimension::exponents(),
+      kind: U::quantity_kind(),
+      factor: U::new(BigRational::one()).quantity().value(),
+      prefixable: false,
+      binary: false,
+    }
+  }
+  pub(crate) fn with_prefixes<U>() -> Self where U: Prefixable<Storage=BigRational> {
+    UnitInfo { prefixable: true, binary: U::takes_binary_prefixes(), ..UnitInfo::of::<U>() }
+  }
+  // Whether the symbol names the unit without a prefix.
+  fn names(&self, symbol: &str) -> bool {
+    names_forms(symbol, &self.shortform, &self.longform) || self.aliases.iter().any(|alias| names_forms(symbol, alias, alias))
+  }
+  // Whether the symbol names the unit with the prefix.
+  fn names_prefixed(&self, symbol: &str, kind: PrefixKind) -> bool {
+    let shortform = format!("{}{}", kind.symbol(), self.shortform);
+    let longform = format!("{}{}", kind.name(), self.longform);
+    names_forms(symbol, &shortform, &longform) || self.aliases.iter().any(|alias| {
+      let alias = format!("{}{}", kind.name(), alias);
+      names_forms(symbol, &alias, &alias)
+    })
+  }
+}
+
+/// A unit found by name, with the prefix applied to it, if any.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Found {
+  /// The prefix, eg kilo in `km`.
+  pub prefix: Option<PrefixKind>,
+  /// The unit.
+  pub unit: UnitInfo,
+}
+
+impl Found {
+  /// The short hand with the prefix, eg `km`.
+  pub fn shortform(&self) -> String {
+    match self.prefix {
+      Some(kind) => format!("{}{}", kind.symbol(), self.unit.shortform),
+      None => self.unit.shortform.clone(),
+    }
+  }
+  /// The size in coherent SI units, including the prefix, eg 1000 for `km`.
+  pub fn factor(&self) -> BigRational {
+    match self.prefix {
+      Some(kind) => kind.factor() * &self.unit.factor,
+      None => self.unit.factor.clone(),
+    }
+  }
+  /// A quantity of the value in this unit, eg `1.5` kilometers.
+  pub fn quantity(&self, value: BigRational) -> DynQuantity {
+    DynQuantity::new(value, self.unit.dimension, self.factor()).with_kind(self.unit.kind)
+  }
+}
+
+/// A reading of a name.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Match {
+  /// A unit, eg `m` as the meter.
+  Unit(Box<Found>),
+  /// A prefix alone, eg `m` as milli.
+  Prefix(PrefixKind),
+}
+
+/// An error looking up a unit by name.
+#[derive(Clone, Debug, PartialEq)]
+pub enum LookupError {
+  /// No unit has the name.
+  Unknown(String),
+  /// More than one unit has the name, with each of them.
+  Ambiguous {
+    name: String,
+    units: Vec<Found>,
+  },
+}
+
+impl fmt::Display for LookupError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      LookupError::Unknown(ref name) => write!(f, "`{}` is not a known unit", name),
+      LookupError::Ambiguous { ref name, ref units } => {
+        let units = units.iter().map(|found| format!("`{}`", found.shortform())).collect::<Vec<_>>();
+        write!(f, "`{}` could be any of {}", name, units.join(", "))
+      },
+    }
+  }
+}
+
+impl Error for LookupError {}
+
+// The units of this crate, from the `registered_units!` of each module, and other names for some of them.
+fn builtin() -> Vec<UnitInfo> {
+  let mut units = base::registered_units();
+  units.extend(derived::registered_units());
+  units.extend(accepted::registered_units());
+  units.extend(information::registered_units());
+  units.extend(customary::registered_units());
+  units.extend(temperature::registered_units());
+  for &(name, alias) in &[("m", "metre"), ("L", "liter"), ("t", "metric ton"), ("A", "amp"), ("s", "sec")] {
+    if let Some(unit) = units.iter_mut().find(|unit| unit.shortform == name) {
+      unit.aliases.push(alias.into());
+    }
+  }
+  units
+}
+
+lazy_static! {
+  static ref REGISTRY: RwLock<Vec<UnitInfo>> = RwLock::new(builtin());
+}
+
+// Add the unit, replacing one of the same forms and dimension.
+fn insert(unit: UnitInfo) {
+  let mut units = REGISTRY.write().unwrap_or_else(PoisonError::into_inner);
+  let same = |other: &UnitInfo| other.shortform == unit.shortform && other.longform == unit.longform && other.dimension == unit.dimension;
+  match units.iter().position(same) {
+    Some(index) => units[index] = unit,
+    None => units.push(unit),
+  }
+}
+
+/// Register a unit that does not take prefixes, so that it is found by its forms.
+pub fn register<U>() where U: IntoQuantity<Storage=BigRational> {
+  insert(UnitInfo::of::<U>());
+}
+
+/// Register a unit that takes prefixes, such as one defined with `base_unit!`, so that it is found by its
+/// forms with or without a prefix.
+pub fn register_prefixable<U>() where U: Prefixable<Storage=BigRational> {
+  insert(UnitInfo::with_prefixes::<U>());
+}
+
+/// Add another name for a registered unit, eg `metre` for `m`. The unit is named by its shortform or
+/// longform.
+pub fn alias(name: &str, alias: &str) -> Result<(), LookupError> {
+  let mut units = REGISTRY.write().unwrap_or_else(PoisonError::into_inner);
+  let matching = units.iter().enumerate()
+    .filter(|&(_, unit)| unit.shortform == name || unit.longform == name)
+    .map(|(index, _)| index)
+    .collect::<Vec<_>>();
+  match matching.len() {
+    0 => Err(LookupError::Unknown(name.into())),
+    1 => {
+      units[matching[0]].aliases.push(alias.into());
+      Ok(())
+    },
+    _ => Err(LookupError::Ambiguous {
+      name: name.into(),
+      units: matching.into_iter().map(|index| Found { prefix: None, unit: units[index].clone() }).collect(),
+    }),
+  }
+}
+
+/// Every registered unit.
+pub fn units() -> Vec<UnitInfo> {
+  REGISTRY.read().unwrap_or_else(PoisonError::into_inner).clone()
+}
+
+/// Every reading of the name: units named directly, then prefixed units, then a prefix alone.
+pub fn find(name: &str) -> Vec<Match> {
+  let name = name.trim();
+  let units = REGISTRY.read().unwrap_or_else(PoisonError::into_inner);
+  let direct = units.iter()
+    .filter(|unit| unit.names(name))
+    .map(|unit| Match::Unit(Box::new(Found { prefix: None, unit: unit.clone() })));
+  let prefixed = units.iter()
+    .filter(|unit| unit.prefixable)
+    .flat_map(|unit| PrefixKind::all().iter().map(move |&kind| (kind, unit)))
+    .filter(|&(kind, unit)| (unit.binary || !kind.is_binary()) && unit.names_prefixed(name, kind))
+    .map(|(kind, unit)| Match::Unit(Box::new(Found { prefix: Some(kind), unit: unit.clone() })));
+  let prefixes = PrefixKind::all().iter()
+    .filter(|kind| names_forms(name, kind.symbol(), kind.name()))
+    .map(|&kind| Match::Prefix(kind));
+  direct.chain(prefixed).chain(prefixes).collect()
+}
+
+/// The unit of the name. A unit named directly always wins over a prefixed unit or a prefix alone, without
+/// an error, so `m` is the meter rather than milli and `ft` the foot rather than a femtotonne; only `find`
+/// shows those other readings. It is an error if no unit, or more than one of the same kind, has the name.
+pub fn lookup(name: &str) -> Result<Found, LookupError> {
+  let units = find(name).into_iter().filter_map(|reading| match reading {
+    Match::Unit(found) => Some(*found),
+    Match::Prefix(_) => None,
+  }).collect::<Vec<_>>();
+  let direct = units.iter().filter(|found| found.prefix.is_none()).cloned().collect::<Vec<_>>();
+  let mut readings = if direct.is_empty() { units } else { direct };
+  match readings.len() {
+    0 => Err(LookupError::Unknown(name.trim().into())),
+    1 => Ok(readings.remove(0)),
+    _ => Err(LookupError::Ambiguous { name: name.trim().into(), units: readings }),
+  }
+}
+
+#[test]
+fn looks_up_every_form() {
+  for name in &["km", "kilometer", "kilometers", "kilometre", " km "] {
+    let found = lookup(name).unwrap();
+    assert_eq!((found.prefix, found.unit.shortform.as_str()), (Some(PrefixKind::Kilo), "m"));
+    assert_eq!(found.factor(), BigRational::from_integer(1_000.into()));
+  }
+  assert_eq!(lookup("kg").unwrap().factor(), BigRational::one());
+  assert_eq!(lookup("µs").unwrap().shortform(), "μs");
+  assert_eq!(lookup("feet").unwrap().unit.longform, "foot");
+  assert_eq!(lookup("MiB").unwrap().factor(), BigRational::from_integer((8 << 20).into()));
+  assert_eq!(lookup("parsec"), Err(LookupError::Unknown("parsec".into())));
+  assert!(lookup("kft").is_err());
+}
+
+#[test]
+fn reports_ambiguity() {
+  let readings = find("m");
+  assert_eq!(readings.len(), 2);
+  assert!(match readings[0] { Match::Unit(ref found) => found.unit.longform == "meter", _ => false });
+  assert_eq!(readings[1], Match::Prefix(PrefixKind::Milli));
+  assert_eq!(lookup("m").unwrap().unit.longform, "meter");
+  assert_eq!(find("ft").len(), 2);
+  assert_eq!(lookup("ft").unwrap().unit.longform, "foot");
+  assert_eq!(lookup("h").unwrap().unit.longform, "hour");
+}
+
+#[test]
+fn registers_the_units_of_each_module() {
+  let units = builtin();
+  let listed = |longform: String| units.iter().find(|unit| unit.longform == longform).cloned();
+  assert_eq!(listed(Tonne::longform()).map(|unit| unit.prefixable), Some(true));
+  assert_eq!(listed(Minute::longform()).map(|unit| unit.prefixable), Some(false));
+  assert_eq!(listed(Byte::longform()).map(|unit| unit.binary), Some(true));
+  assert_eq!(listed(Kelvin::longform()).map(|unit| unit.prefixable), Some(true));
+  assert_eq!(listed("degree Celsius".into()), None);
+  assert_eq!(units.len(), 57);
+}
+
+#[test]
+fn builtin_units_have_distinct_names() {
+  let units = builtin();
+  for (index, unit) in units.iter().enumerate() {
+    for other in &units[index + 1..] {
+      assert!(!other.names(&unit.shortform) && !other.names(&unit.longform), "{} and {}", unit.longform, other.longform);
+    }
+  }
+}
+
+#[test]
+fn enumerates_units_with_dimension_and_factor() {
+  let units = units();
+  let hour = units.iter().find(|unit| unit.longform == "hour").unwrap();
+  assert_eq!((hour.dimension, hour.factor.clone()), ([0, 0, 1, 0, 0, 0, 0], BigRational::from_integer(3_600.into())));
+  assert!(units.iter().any(|unit| unit.shortform == "N" && unit.prefixable));
+  let quantity = lookup("mi").unwrap().quantity(BigRational::from_integer(2.into()));
+  assert_eq!(quantity.to_unit::<Meter>(), Ok(Meter::from(3_218_688) / 1_000i64));
+  let size = lookup("KiB").unwrap().quantity(BigRational::from_integer(2.into()));
+  assert_eq!(size.to_unit::<Byte>(), Ok(Byte::from(2_048)));
+  assert!(size.to_unit::<Radian>().is_err());
+}
@@ -0,0 +1,141 @@
+//! Serialization of units with serde, behind the `serde` feature.
+//!
+//! Units serialize exactly by default, eg `{ "num": "3", "den": "4", "unit": "km" }`. The `compact` and
+//! `float` modules are other representations, used with `#[serde(with = "si::serialization::compact")]`.
+//!
+//! Deserializing checks the unit, accepting the expected unit or any prefix of its base and converting it,
+//! so `"750 m"` reads into a `Kilo<Meter>` while `"750 s"` is an error.
+use std::str::FromStr;
+use num::Zero;
+use num::bigint::BigInt;
+use num::rational::BigRational;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::de::Error as DeError;
+use serde::ser::Error as SerError;
+use parse::ParseError;
+use storage::Storage;
+use Unit;
+#[cfg(test)] use base::*;
+#[cfg(test)] use prefix::*;
+#[cfg(test)] use serde_json;
+
+// The value of a unit, or an error if it is not a finite number.
+fn rational<U, E>(unit: &U) -> Result<BigRational, E> where U: Unit, E: SerError {
+  unit.value_ref().to_decimal().ok_or_else(|| E::custom("the value is not a finite number"))
+}
+
+// The value measured in the named unit, converted into the expected one.
+fn in_unit<U>(value: &BigRational, unit: &str) -> Result<U, ParseError> where U: FromStr<Err=ParseError> {
+  format!("{}/{} {}", value.numer(), value.denom(), unit).parse()
+}
+
+#[derive(Serialize, Deserialize)]
+struct Exact {
+  num: String,
+  den: String,
+  unit: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Float {
+  value: f64,
+  unit: String,
+}
+
+/// The exact representation, eg `{ "num": "3", "den": "4", "unit": "km" }`. The numerator and denominator
+/// are strings, as they may be larger than a JSON number can hold. This is the default.
+pub mod exact {
+  use super::*;
+
+  /// Serialize the unit exactly.
+  pub fn serialize<U, Z>(unit: &U, serializer: Z) -> Result<Z::Ok, Z::Error> where U: Unit, Z: Serializer {
+    let value = rational::<U, Z::Error>(unit)?;
+    Exact { num: value.numer().to_string(), den: value.denom().to_string(), unit: U::shortform() }.serialize(serializer)
+  }
+
+  /// Deserialize the unit exactly.
+  pub fn deserialize<'de, U, D>(deserializer: D) -> Result<U, D::Error> where U: FromStr<Err=ParseError>, D: Deserializer<'de> {
+    let exact = Exact::deserialize(deserializer)?;
+    let numerator = exact.num.parse::<BigInt>().map_err(|_| D::Error::custom(ParseError::InvalidNumber(exact.num.clone())))?;
+    let denominator = exact.den.parse::<BigInt>().map_err(|_| D::Error::custom(ParseError::InvalidNumber(exact.den.clone())))?;
+    if denominator.is_zero() {
+      return Err(D::Error::custom(ParseError::ZeroDenominator));
+    }
+    in_unit(&BigRational::new(numerator, denominator), &exact.unit).map_err(D::Error::custom)
+  }
+}
+
+/// The compact representation, a string such as `"3/4 km"` or `"2 km"`.
+pub mod compact {
+  use super::*;
+
+  /// Serialize the unit as a string.
+  pub fn serialize<U, Z>(unit: &U, serializer: Z) -> Result<Z::Ok, Z::Error> where U: Unit, Z: Serializer {
+    let value = rational::<U, Z::Error>(unit)?;
+    if value.is_integer() {
+      serializer.collect_str(&format_args!("{} {}", value.numer(), U::shortform()))
+    } else {
+      serializer.collect_str(&format_args!("{}/{} {}", value.numer(), value.denom(), U::shortform()))
+    }
+  }
+
+  /// Deserialize the unit from a string, as with `FromStr`.
+  pub fn deserialize<'de, U, D>(deserializer: D) -> Result<U, D::Error> where U: FromStr<Err=ParseError>, D: Deserializer<'de> {
+    String::deserialize(deserializer)?.parse().map_err(D::Error::custom)
+  }
+}
+
+/// The lossy floating point representation, eg `{ "value": 0.75, "unit": "km" }`. Values round to the
+/// nearest `f64`.
+pub mod float {
+  use super::*;
+  use num::ToPrimitive;
+
+  /// Serialize the unit as the nearest `f64`.
+  pub fn serialize<U, Z>(unit: &U, serializer: Z) -> Result<Z::Ok, Z::Error> where U: Unit, Z: Serializer {
+    let value = rational::<U, Z::Error>(unit)?;
+    let value = value.to_f64().ok_or_else(|| Z::Error::custom("the value is too large for f64"))?;
+    Float { value, unit: U::shortform() }.serialize(serializer)
+  }
+
+  /// Deserialize the unit from an `f64`.
+  pub fn deserialize<'de, U, D>(deserializer: D) -> Result<U, D::Error> where U: FromStr<Err=ParseError>, D: Deserializer<'de> {
+    let float = Float::deserialize(deserializer)?;
+    let value = BigRational::from_float(float.value).ok_or_else(|| D::Error::custom(ParseError::InvalidNumber(float.value.to_string())))?;
+    in_unit(&value, &float.unit).map_err(D::Error::custom)
+  }
+}
+
+#[test]
+fn serializes_exactly_by_default() {
+  let distance = Kilo::<Meter>::from(3) / 4;
+  let json = serde_json::to_string(&distance).unwrap();
+  assert_eq!(json, r#"{"num":"3","den":"4","unit":"km"}"#);
+  assert_eq!(serde_json::from_str::<Kilo<Meter>>(&json).unwrap(), distance);
+  assert_eq!(serde_json::from_str::<Meter>(&json).unwrap(), Meter::from(750));
+}
+
+#[test]
+fn validates_the_unit() {
+  let error = serde_json::from_str::<Meter>(r#"{"num":"1","den":"1","unit":"s"}"#).unwrap_err();
+  assert!(error.to_string().contains("`s` is not a unit of `m`"));
+  assert!(serde_json::from_str::<Meter>(r#"{"num":"1","den":"0","unit":"m"}"#).is_err());
+  assert!(serde_json::from_str::<Meter>(r#"{"num":"x","den":"1","unit":"m"}"#).is_err());
+}
+
+#[test]
+fn has_compact_and_float_forms() {
+  #[derive(Serialize, Deserialize, Debug, PartialEq)]
+  struct Trip {
+    #[serde(with = "compact")]
+    distance: Kilo<Meter>,
+    #[serde(with = "float")]
+    time: Second,
+  }
+  let trip = Trip { distance: Kilo::<Meter>::from(3) / 4, time: Second::from(3) / 2 };
+  let json = serde_json::to_string(&trip).unwrap();
+  assert_eq!(json, r#"{"distance":"3/4 km","time":{"value":1.5,"unit":"s"}}"#);
+  assert_eq!(serde_json::from_str::<Trip>(&json).unwrap(), trip);
+  let trip = serde_json::from_str::<Trip>(r#"{"distance":"500 m","time":{"value":250,"unit":"ms"}}"#).unwrap();
+  assert_eq!((trip.distance, trip.time), (Kilo::<Meter>::from(1) / 2, Second::from(1) / 4));
+}
@@ -0,0 +1,250 @@
+//! The numeric storage of units.
+//!
+//! Units store a `BigRational` by default, which is exact but allocates. Units are generic over any
+//! `Storage`, so hot paths can instead use `f64`, `f32`, `i64` or `Ratio<i64>`. The default units such as
+//! `base::Meter` are exact; the generic forms live in their modules, eg `base::meter::Meter<f64>`.
+//!
+//! Prefixes store the same type as their base, so `Kilo<meter::Meter<f64>>` holds an `f64`.
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::*;
+use std::mem;
+use num::{One, ToPrimitive};
+use num::bigint::BigInt;
+use num::rational::{BigRational, Ratio};
+use parse::parse_number;
+
+/// A numeric type that a unit can store its value in.
+pub trait Storage: Clone + fmt::Debug + fmt::Display + PartialEq + PartialOrd
++ Add<Output=Self> + Sub<Output=Self> + Mul<Output=Self> + Div<Output=Self> {
+  /// The nearest representable value to the rational. Floating point types round to nearest, while
+  /// integers truncate towards zero.
+  ///
+  /// # Panics
+  ///
+  /// Panics if an integer or ratio of integers cannot hold the value.
+  fn from_rational(value: &BigRational) -> Self;
+  /// The integer, converted natively rather than through a `BigRational`.
+  fn from_i64(value: i64) -> Self {
+    Self::from_rational(&BigRational::from_integer(BigInt::from(value)))
+  }
+  /// Zero, eg to start a sum.
+  fn zero() -> Self {
+    Self::from_i64(0)
+  }
+  /// The exact value, or `None` if it is not a finite number.
+  fn to_rational(&self) -> Option<BigRational>;
+  /// The value shown when formatting. Floating point values show the shortest decimal that converts back
+  /// to the same value, rather than their exact binary expansion.
+  fn to_decimal(&self) -> Option<BigRational> {
+    self.to_rational()
+  }
+  /// Multiply by an exact factor, such as that of a prefix.
+  fn scale(self, factor: &BigRational) -> Self {
+    self * Self::from_rational(factor)
+  }
+  /// Multiply by a prepared factor, such as that of a prefix, without converting it on every call.
+  fn scale_by(self, factor: &Factor) -> Self {
+    self.scale(factor.exact())
+  }
+}
+
+/// An exact factor prepared for scaling each storage, so the constant factors of prefixes and units are
+/// converted once rather than on every scaling.
+#[derive(Clone, Debug)]
+pub struct Factor {
+  exact: BigRational,
+  f64: (f64, f64),
+  f32: (f32, f32),
+  integers: Option<(i128, i128)>,
+}
+
+impl Factor {
+  pub fn new(exact: BigRational) -> Self {
+    let nan = |value: Option<f64>| value.unwrap_or(f64::NAN);
+    Factor {
+      f64: (nan(exact.numer().to_f64()), nan(exact.denom().to_f64())),
+      f32: (exact.numer().to_f32().unwrap_or(f32::NAN), exact.denom().to_f32().unwrap_or(f32::NAN)),
+      integers: exact.numer().to_i128().and_then(|numerator| exact.denom().to_i128().map(|denominator| (numerator, denominator))),
+      exact,
+    }
+  }
+  /// The exact factor.
+  pub fn exact(&self) -> &BigRational {
+    &self.exact
+  }
+  /// The inverse factor.
+  pub fn recip(&self) -> Self {
+    Factor::new(self.exact.recip())
+  }
+}
+
+// Zero in any storage, eg to start a sum.
+#[doc(hidden)]
+pub fn zero<S>() -> S where S: Storage {
+  S::zero()
+}
+
+// A primitive integer in any storage, natively when it fits an `i64`.
+#[doc(hidden)]
+pub fn from_integer<S, I>(value: I) -> S where S: Storage, I: Copy + Into<BigInt>, i64: TryFrom<I> {
+  match i64::try_from(value) {
+    Ok(value) => S::from_i64(value),
+    Err(_) => S::from_rational(&BigRational::from_integer(value.into())),
+  }
+}
+
+// Take the value, leaving zero in its place, so that compound assignment operates on the value without cloning
+// it.
+#[doc(hidden)]
+pub fn take<S>(value: &mut S) -> S where S: Storage {
+  mem::replace(value, zero())
+}
+
+impl Storage for BigRational {
+  fn from_rational(value: &BigRational) -> Self {
+    value.clone()
+  }
+  fn from_i64(value: i64) -> Self {
+    BigRational::from_integer(BigInt::from(value))
+  }
+  fn to_rational(&self) -> Option<BigRational> {
+    Some(self.clone())
+  }
+  fn scale(self, factor: &BigRational) -> Self {
+    if factor.is_one() { self } else { self * factor }
+  }
+}
+
+macro_rules! float_storage {
+  ($float:ident, $to_float:ident) => (
+    impl Storage for $float {
+      fn from_rational(value: &BigRational) -> Self {
+        value.$to_float().unwrap_or(<$float>::NAN)
+      }
+      fn from_i64(value: i64) -> Self {
+        value as $float
+      }
+      fn to_rational(&self) -> Option<BigRational> {
+        BigRational::from_float(*self)
+      }
+      fn to_decimal(&self) -> Option<BigRational> {
+        if self.is_finite() { parse_number(&format!("{:e}", self)).ok() } else { None }
+      }
+      // Prefix factors are powers of ten, so dividing by the denominator avoids rounding `10^-n` first.
+      fn scale(self, factor: &BigRational) -> Self {
+        let numerator = factor.numer().$to_float().unwrap_or(<$float>::NAN);
+        let denominator = factor.denom().$to_float().unwrap_or(<$float>::NAN);
+        self * numerator / denominator
+      }
+      fn scale_by(self, factor: &Factor) -> Self {
+        let (numerator, denominator) = factor.$float;
+        self * numerator / denominator
+      }
+    }
+  )
+}
+
+float_storage!(f64, to_f64);
+float_storage!(f32, to_f32);
+
+impl Storage for i64 {
+  fn from_rational(value: &BigRational) -> Self {
+    value.to_integer().to_i64().expect("the value is too large for i64 storage")
+  }
+  fn from_i64(value: i64) -> Self {
+    value
+  }
+  fn to_rational(&self) -> Option<BigRational> {
+    Some(BigRational::from_integer(BigInt::from(*self)))
+  }
+  // The result truncates towards zero, dropping the remainder, so `1500 m` is `1 km` and `-1500 m` is `-1 km`.
+  // Multiplying before dividing keeps the factor itself exact, as `1/1000` truncated alone would give `0 km`.
+  fn scale(self, factor: &BigRational) -> Self {
+    let product = factor.numer().to_i128().and_then(|numerator| (self as i128).checked_mul(numerator));
+    match (product, factor.denom().to_i128()) {
+      (Some(product), Some(denominator)) => (product / denominator).to_i64().expect("the value is too large for i64 storage"),
+      _ => Self::from_rational(&(BigRational::from_integer(BigInt::from(self)) * factor)),
+    }
+  }
+  fn scale_by(self, factor: &Factor) -> Self {
+    match factor.integers.and_then(|(numerator, denominator)| (self as i128).checked_mul(numerator).map(|product| product / denominator)) {
+      Some(value) => value.to_i64().expect("the value is too large for i64 storage"),
+      None => self.scale(factor.exact()),
+    }
+  }
+}
+
+impl Storage for Ratio<i64> {
+  fn from_rational(value: &BigRational) -> Self {
+    match (value.numer().to_i64(), value.denom().to_i64()) {
+      (Some(numerator), Some(denominator)) => Ratio::new(numerator, denominator),
+      _ => panic!("the value is too large for Ratio<i64> storage"),
+    }
+  }
+  fn to_rational(&self) -> Option<BigRational> {
+    Some(BigRational::new(BigInt::from(*self.numer()), BigInt::from(*self.denom())))
+  }
+  fn from_i64(value: i64) -> Self {
+    Ratio::from_integer(value)
+  }
+  // Scale exactly, as the factor alone may not fit, eg `10^-24` for yocto.
+  fn scale(self, factor: &BigRational) -> Self {
+    match (factor.numer().to_i128(), factor.denom().to_i128()) {
+      (Some(numerator), Some(denominator)) => scale_ratio(&self, numerator, denominator),
+      _ => None,
+    }.unwrap_or_else(|| Self::from_rational(&(self.to_rational().expect("ratios are finite") * factor)))
+  }
+  fn scale_by(self, factor: &Factor) -> Self {
+    match factor.integers {
+      Some((numerator, denominator)) => scale_ratio(&self, numerator, denominator),
+      None => None,
+    }.unwrap_or_else(|| self.scale(factor.exact()))
+  }
+}
+
+// Scale a ratio in `i128`, which holds the product of any ratio and prefix factor, or `None` if that
+// overflows.
+fn scale_ratio(value: &Ratio<i64>, numerator: i128, denominator: i128) -> Option<Ratio<i64>> {
+  let numerator = (*value.numer() as i128).checked_mul(numerator)?;
+  let denominator = (*value.denom() as i128).checked_mul(denominator)?;
+  let scaled = Ratio::new(numerator, denominator);
+  match (scaled.numer().to_i64(), scaled.denom().to_i64()) {
+    (Some(numerator), Some(denominator)) => Some(Ratio::new_raw(numerator, denominator)),
+    _ => panic!("the value is too large for Ratio<i64> storage"),
+  }
+}
+
+#[test]
+fn integers_multiply_before_dividing() {
+  let milli = BigRational::new(BigInt::from(1), BigInt::from(1000));
+  assert_eq!(1500i64.scale(&milli), 1);
+  assert_eq!((-1500i64).scale(&milli), -1);
+  assert_eq!(7i64.scale(&BigRational::new(BigInt::from(3), BigInt::from(2))), 10);
+}
+
+#[test]
+fn floats_format_as_shortest_decimal() {
+  assert_eq!(0.1f64.to_decimal(), Some(BigRational::new(BigInt::from(1), BigInt::from(10))));
+  assert!(0.1f64.to_rational() != 0.1f64.to_decimal());
+  assert_eq!(f64::NAN.to_rational(), None);
+}
+
+#[test]
+fn ratios_scale_exactly() {
+  let milli = BigRational::new(BigInt::from(1), BigInt::from(1000));
+  let value = Ratio::new(3i64, 2);
+  assert_eq!(value.scale(&milli), Ratio::new(3, 2000));
+  assert_eq!(value.scale(&milli).scale(&milli.recip()), value);
+}
+
+#[test]
+fn prepared_factors_scale_natively() {
+  let yocto = Factor::new(BigRational::new(BigInt::from(1), BigInt::from(10).pow(24)));
+  assert_eq!(Ratio::new(3, 2 * 10i64.pow(18)).scale_by(&yocto.recip()), Ratio::from_integer(1_500_000));
+  assert_eq!(1.5f64.scale_by(&yocto.recip()), 1.5e24);
+  assert_eq!(2_000_000i64.scale_by(&Factor::new(BigRational::new(BigInt::from(1), BigInt::from(1000)))), 2_000);
+  assert_eq!(zero::<f64>(), 0.0);
+  assert_eq!(from_integer::<Ratio<i64>, u64>(u64::MAX / 2), Ratio::from_integer(i64::MAX));
+  assert_eq!(from_integer::<f64, u64>(u64::MAX), 18_446_744_073_709_551_615.0);
+}
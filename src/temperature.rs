@@ -0,0 +1,458 @@
+//! Temperature scales with an offset from absolute zero: Celsius, Fahrenheit and Rankine.
+//!
+//! An absolute temperature such as `DegreeCelsius` is a point on its scale. Adding or subtracting a
+//! temperature difference, such as `CelsiusDifference` or `KelvinDifference`, moves the point, and subtracting
+//! two points of the same scale gives their difference. Adding two points does not compile, nor does adding a
+//! `Kelvin`, which is a thermodynamic temperature rather than a difference:
+//!
+//! ```compile_fail
+//! # extern crate si;
+//! # use si::temperature::*;
+//! # fn main() {
+//! let _ = DegreeCelsius::from(20) + DegreeCelsius::from(20);
+//! # }
+//! ```
+//!
+//! ```compile_fail
+//! # extern crate si;
+//! # use si::base::Kelvin;
+//! # use si::temperature::*;
+//! # fn main() {
+//! let _ = DegreeCelsius::from(20) + Kelvin::from(300);
+//! # }
+//! ```
+//!
+//! Differences convert into each other, and into a `Temperature` quantity for arithmetic such as with a heat
+//! capacity. No quantity converts into a difference, so a `Kelvin` does not become one, and a `DynQuantity`
+//! of a difference does not convert into a `Kelvin`:
+//!
+//! ```compile_fail
+//! # extern crate si;
+//! # use si::base::Kelvin;
+//! # use si::quantity::IntoQuantity;
+//! # use si::temperature::*;
+//! # fn main() {
+//! let _ = CelsiusDifference::from(Kelvin::from(300).quantity());
+//! # }
+//! ```
+use quantity::IntoQuantity;
+use storage::Storage;
+#[cfg(test)] use base::Kelvin;
+#[cfg(test)] use num::rational::BigRational;
+
+/// A difference between two temperatures, which moves an absolute temperature along its scale.
+pub trait Difference: IntoQuantity<Dimension=<::quantity::Temperature as IntoQuantity>::Dimension> {}
+
+macro_rules! temperature_from_primitives {
+  {
+    $unit:ident,
+    [$($primitive:ty,)*]
+  } => {
+    $(
+      impl<S> From<$primitive> for $unit<S> where S: Storage {
+        fn from(value: $primitive) -> Self {
+          Self::new(::storage::from_integer(value))
+        }
+      }
+    )*
+  }
+}
+
+macro_rules! temperature_from_floats {
+  {
+    $unit:ident,
+    [$($float:ty,)*]
+  } => {
+    $(
+      impl<S> ::std::convert::TryFrom<$float> for $unit<S> where S: Storage {
+        type Error = ::convert::ConversionError;
+        fn try_from(value: $float) -> Result<Self, Self::Error> {
+          ::convert::exact(value.into()).map(Self::from)
+        }
+      }
+    )*
+  }
+}
+
+macro_rules! temperature_into_integers {
+  {
+    $unit:ident,
+    [$($primitive:ty,)*]
+  } => {
+    $(
+      impl<S> ::std::convert::TryFrom<$unit<S>> for $primitive where S: Storage {
+        type Error = ::convert::ConversionError;
+        fn try_from(unit: $unit<S>) -> Result<Self, Self::Error> {
+          ::convert::integer(&unit.value)
+        }
+      }
+    )*
+  }
+}
+
+macro_rules! generate_absolute_temperature {
+  {
+    name       = $name:ident,
+    module     = $module:ident,
+    longform   = $longform:expr,
+    shortform  = $shortform:expr,
+    difference = $difference_module:ident::$difference:ident,
+    offset     = ($numerator:expr, $denominator:expr),
+    $doc:meta,
+  } => (
+    #[doc = concat!("The ", $longform, ", generic over its numeric storage.")]
+    pub mod $module {
+      use Unit;
+      use base::kelvin::Kelvin;
+      use format;
+      use num::bigint::BigInt;
+      use num::rational::BigRational;
+      use parse::{self, ParseError};
+      use quantity::IntoQuantity;
+      use std::cmp::Ordering;
+      use std::fmt;
+      use std::ops::*;
+      use std::str::FromStr;
+      use storage::Storage;
+      use super::$difference_module::$difference;
+      use super::Difference;
+
+      lazy_static! {
+        static ref SHORTFORM: &'static str = $shortform;
+        static ref LONGFORM: &'static str = $longform;
+        // The temperature of absolute zero, negated, eg 273.15 for the Celsius scale.
+        static ref OFFSET: BigRational = BigRational::new(BigInt::from($numerator), BigInt::from($denominator));
+      }
+
+      #[$doc]
+      #[derive(Clone, Debug)]
+      pub struct $name<S = BigRational> where S: Storage {
+        value: S,
+      }
+
+      impl<S> $name<S> where S: Storage {
+        /// Create a temperature from its value on the scale.
+        pub fn new(value: S) -> Self {
+          $name {
+            value
+          }
+        }
+        /// Get the value on the scale.
+        pub fn value(self) -> S {
+          self.value
+        }
+        /// Get a reference to the value on the scale.
+        pub fn value_ref(&self) -> &S {
+          &self.value
+        }
+        /// The full string for the scale, eg `degree Celsius`.
+        pub fn longform() -> String {
+          (*LONGFORM).into()
+        }
+        /// The short hand for the scale, eg `°C`.
+        pub fn shortform() -> String {
+          (*SHORTFORM).into()
+        }
+        /// The thermodynamic temperature, measured from absolute zero.
+        pub fn kelvin(self) -> Kelvin<S> {
+          let above_zero = $difference::new(self.value + S::from_rational(&OFFSET));
+          Kelvin::from(above_zero.quantity())
+        }
+      }
+
+      //
+      // Conversions
+      //
+      impl<S> From<BigInt> for $name<S> where S: Storage {
+        fn from(value: BigInt) -> Self {
+          Self::from(BigRational::from_integer(value))
+        }
+      }
+
+      impl<S> From<BigRational> for $name<S> where S: Storage {
+        fn from(value: BigRational) -> Self {
+          Self::new(S::from_rational(&value))
+        }
+      }
+
+      temperature_from_primitives! { $name, [i64, u64, i32, u32, i16, u16, i8, u8, isize, usize,] }
+      temperature_from_floats! { $name, [f64, f32,] }
+      temperature_into_integers! { $name, [i64, u64, i32, u32, i16, u16, i8, u8, i128, u128, isize, usize,] }
+
+      impl<S> From<Kelvin<S>> for $name<S> where S: Storage {
+        fn from(value: Kelvin<S>) -> Self {
+          let above_zero = $difference::from_quantity(value.quantity());
+          Self::new(above_zero.value() - S::from_rational(&OFFSET))
+        }
+      }
+
+      impl<S> From<$name<S>> for Kelvin<S> where S: Storage {
+        fn from(value: $name<S>) -> Self {
+          value.kelvin()
+        }
+      }
+
+      //
+      // Operations with differences
+      //
+      impl<S,U> Add<U> for $name<S> where S: Storage, U: Difference<Storage=S> {
+        type Output = Self;
+        fn add(self, difference: U) -> Self {
+          Self::new(self.value + $difference::from_quantity(difference.quantity()).value())
+        }
+      }
+
+      impl<S,U> Sub<U> for $name<S> where S: Storage, U: Difference<Storage=S> {
+        type Output = Self;
+        fn sub(self, difference: U) -> Self {
+          Self::new(self.value - $difference::from_quantity(difference.quantity()).value())
+        }
+      }
+
+      impl<S> Sub for $name<S> where S: Storage {
+        type Output = $difference<S>;
+        fn sub(self, other: Self) -> $difference<S> {
+          $difference::new(self.value - other.value)
+        }
+      }
+
+      //
+      // Formatting
+      //
+      impl<S> fmt::Display for $name<S> where S: Storage {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+          format::write_value(&self.value, Self::shortform(), Self::longform(), f, format::number)
+        }
+      }
+
+      impl<S> fmt::LowerExp for $name<S> where S: Storage {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+          format::write_value(&self.value, Self::shortform(), Self::longform(), f, format::scientific_number)
+        }
+      }
+
+      impl<S> fmt::UpperExp for $name<S> where S: Storage {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+          format::write_value(&self.value, Self::shortform(), Self::longform(), f, format::upper_scientific_number)
+        }
+      }
+
+      impl<S> FromStr for $name<S> where S: Storage {
+        type Err = ParseError;
+        fn from_str(input: &str) -> Result<Self, ParseError> {
+          parse::parse_value(input, &Self::shortform(), &Self::longform()).map(Self::from)
+        }
+      }
+
+      //
+      // Equals and ordering, on the same scale
+      //
+      impl<S> PartialEq for $name<S> where S: Storage {
+        fn eq(&self, other: &Self) -> bool {
+          self.value == other.value
+        }
+      }
+
+      impl<S> Eq for $name<S> where S: Storage + Eq {}
+
+      impl<S> PartialOrd for $name<S> where S: Storage {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+          self.value.partial_cmp(&other.value)
+        }
+      }
+
+      impl<S> Ord for $name<S> where S: Storage + Ord {
+        fn cmp(&self, other: &Self) -> Ordering {
+          self.value.cmp(&other.value)
+        }
+      }
+
+      #[test]
+      fn has_right_forms() {
+        assert_eq!(<$name>::shortform(), $shortform);
+        assert_eq!(<$name>::longform(), $longform);
+      }
+
+      #[test]
+      fn into_kelvin_and_back_is_equal() {
+        let value = <$name>::from(-40);
+        assert_eq!(<$name>::from(value.clone().kelvin()), value);
+        assert_eq!(<$name>::from(Kelvin::from(0)), <$name>::new(-OFFSET.clone()));
+      }
+
+      #[test]
+      fn can_add_and_sub_differences() {
+        let value = <$name>::from(20);
+        assert_eq!(value.clone() + $difference::from(5), <$name>::from(25));
+        assert_eq!(value.clone() - $difference::from(5), <$name>::from(15));
+        assert_eq!(<$name>::from(25) - value, $difference::from(5));
+      }
+
+      #[test]
+      fn can_display_and_parse() {
+        let value = <$name>::new(BigRational::new(BigInt::from(41), BigInt::from(2)));
+        assert_eq!(format!("{}", value), format!("20.5 {}", $shortform));
+        assert_eq!(format!("{}", value).parse::<$name>(), Ok(value));
+        assert_eq!(format!("{:#}", <$name>::from(1)), format!("1 {}", $longform));
+      }
+    }
+    #[$doc]
+    pub type $name = self::$module::$name<::num::rational::BigRational>;
+  )
+}
+
+macro_rules! temperature_conversions {
+  {
+    $($from_module:ident::$from:ident => $into_module:ident::$into:ident,)*
+  } => {
+    $(
+      impl<S> From<$from_module::$from<S>> for $into_module::$into<S> where S: ::storage::Storage {
+        fn from(value: $from_module::$from<S>) -> Self {
+          Self::from(value.kelvin())
+        }
+      }
+    )*
+  }
+}
+
+registered_units! {
+  generate_derived! {
+    name      = KelvinDifference,
+    module    = kelvin_difference,
+    longform  = "kelvin difference",
+    shortform = "ΔK",
+    dimension = Temperature,
+    kind      = TemperatureDifference,
+    doc       = "A difference between thermodynamic temperatures, equal to K.",
+  }
+
+  generate_derived! {
+    name      = CelsiusDifference,
+    module    = celsius_difference,
+    longform  = "degree Celsius difference",
+    shortform = "Δ°C",
+    dimension = Temperature,
+    kind      = TemperatureDifference,
+    doc       = "A difference between temperatures on the Celsius scale, equal to K.",
+  }
+
+  generate_derived! {
+    name      = FahrenheitDifference,
+    module    = fahrenheit_difference,
+    longform  = "degree Fahrenheit difference",
+    shortform = "Δ°F",
+    dimension = Temperature,
+    factor    = (5, 9),
+    kind      = TemperatureDifference,
+    doc       = "A difference between temperatures on the Fahrenheit scale, equal to 5/9 K.",
+  }
+
+  generate_derived! {
+    name      = RankineDifference,
+    module    = rankine_difference,
+    longform  = "degree Rankine difference",
+    shortform = "Δ°R",
+    dimension = Temperature,
+    factor    = (5, 9),
+    kind      = TemperatureDifference,
+    doc       = "A difference between temperatures on the Rankine scale, equal to 5/9 K.",
+  }
+
+  generate_absolute_temperature! {
+    name       = DegreeCelsius,
+    module     = degree_celsius,
+    longform   = "degree Celsius",
+    shortform  = "°C",
+    difference = celsius_difference::CelsiusDifference,
+    offset     = (27315, 100),
+    doc        = "A temperature on the Celsius scale, where water freezes at 0 °C and absolute zero is -273.15 °C.",
+  }
+
+  generate_absolute_temperature! {
+    name       = DegreeFahrenheit,
+    module     = degree_fahrenheit,
+    longform   = "degree Fahrenheit",
+    shortform  = "°F",
+    difference = fahrenheit_difference::FahrenheitDifference,
+    offset     = (45967, 100),
+    doc        = "A temperature on the Fahrenheit scale, where water freezes at 32 °F and absolute zero is -459.67 °F.",
+  }
+
+  generate_absolute_temperature! {
+    name       = DegreeRankine,
+    module     = degree_rankine,
+    longform   = "degree Rankine",
+    shortform  = "°R",
+    difference = rankine_difference::RankineDifference,
+    offset     = (0, 1),
+    doc        = "A temperature on the Rankine scale, which starts at absolute zero in steps of a degree Fahrenheit.",
+  }
+}
+
+impl<S> Difference for kelvin_difference::KelvinDifference<S> where S: Storage {}
+impl<S> Difference for celsius_difference::CelsiusDifference<S> where S: Storage {}
+impl<S> Difference for fahrenheit_difference::FahrenheitDifference<S> where S: Storage {}
+impl<S> Difference for rankine_difference::RankineDifference<S> where S: Storage {}
+
+// Differences convert between scales through their quantity, which only they may convert from.
+macro_rules! difference_conversions {
+  {
+    $($from_module:ident::$from:ident => $into_module:ident::$into:ident,)*
+  } => {
+    $(
+      impl<S> From<$from_module::$from<S>> for $into_module::$into<S> where S: ::storage::Storage {
+        fn from(value: $from_module::$from<S>) -> Self {
+          Self::from_quantity(value.quantity())
+        }
+      }
+    )*
+  }
+}
+
+difference_conversions! {
+  celsius_difference::CelsiusDifference => kelvin_difference::KelvinDifference,
+  fahrenheit_difference::FahrenheitDifference => kelvin_difference::KelvinDifference,
+  rankine_difference::RankineDifference => kelvin_difference::KelvinDifference,
+  kelvin_difference::KelvinDifference => celsius_difference::CelsiusDifference,
+  fahrenheit_difference::FahrenheitDifference => celsius_difference::CelsiusDifference,
+  rankine_difference::RankineDifference => celsius_difference::CelsiusDifference,
+  kelvin_difference::KelvinDifference => fahrenheit_difference::FahrenheitDifference,
+  celsius_difference::CelsiusDifference => fahrenheit_difference::FahrenheitDifference,
+  rankine_difference::RankineDifference => fahrenheit_difference::FahrenheitDifference,
+  kelvin_difference::KelvinDifference => rankine_difference::RankineDifference,
+  celsius_difference::CelsiusDifference => rankine_difference::RankineDifference,
+  fahrenheit_difference::FahrenheitDifference => rankine_difference::RankineDifference,
+}
+
+temperature_conversions! {
+  degree_fahrenheit::DegreeFahrenheit => degree_celsius::DegreeCelsius,
+  degree_rankine::DegreeRankine => degree_celsius::DegreeCelsius,
+  degree_celsius::DegreeCelsius => degree_fahrenheit::DegreeFahrenheit,
+  degree_rankine::DegreeRankine => degree_fahrenheit::DegreeFahrenheit,
+  degree_celsius::DegreeCelsius => degree_rankine::DegreeRankine,
+  degree_fahrenheit::DegreeFahrenheit => degree_rankine::DegreeRankine,
+}
+
+#[test]
+fn converts_between_scales() {
+  assert_eq!(DegreeFahrenheit::from(DegreeCelsius::from(100)), DegreeFahrenheit::from(212));
+  assert_eq!(DegreeCelsius::from(DegreeFahrenheit::from(-40)), DegreeCelsius::from(-40));
+  assert_eq!(DegreeRankine::from(DegreeCelsius::from(0)), DegreeRankine::from(BigRational::new(49_167.into(), 100.into())));
+  assert_eq!(Kelvin::from(DegreeCelsius::from(0)), Kelvin::from(27_315) / 100);
+}
+
+#[test]
+fn adds_any_difference() {
+  assert_eq!(DegreeCelsius::from(20) + KelvinDifference::from(5), DegreeCelsius::from(25));
+  assert_eq!(DegreeFahrenheit::from(212) - KelvinDifference::from(100), DegreeFahrenheit::from(32));
+  assert_eq!(DegreeCelsius::from(20) + FahrenheitDifference::from(9), DegreeCelsius::from(25));
+  assert_eq!(DegreeFahrenheit::from(32) + CelsiusDifference::from(100), DegreeFahrenheit::from(212));
+  assert_eq!((DegreeCelsius::from(30) - DegreeCelsius::from(20)).quantity(), Kelvin::from(10).quantity());
+}
+
+#[test]
+fn converts_between_differences() {
+  assert_eq!(CelsiusDifference::from(FahrenheitDifference::from(9)), CelsiusDifference::from(5));
+  assert_eq!(RankineDifference::from(KelvinDifference::from(5)), RankineDifference::from(9));
+  assert_eq!(KelvinDifference::from(CelsiusDifference::from(3)).quantity(), Kelvin::from(3).quantity());
+}
@@ -0,0 +1,82 @@
+//! Totals of values in any units of the same base, eg meters and millimeters read from different sources.
+//!
+//! A `Total` adds each value into a single value of the base, rather than building a new unit for every
+//! element. Units of one type can also be summed directly, eg `readings.iter().sum::<Kilo<Meter>>()`. Only
+//! dimensionless quantities have a product, eg `scales.iter().product::<Dimensionless>()`, since the
+//! dimension of a product of lengths depends on how many there are.
+use std::iter::FromIterator;
+use std::ops::AddAssign;
+use {Unit, IntoBase};
+use storage;
+#[cfg(test)] use base::*;
+#[cfg(test)] use prefix::*;
+
+/// A running total in a base unit of values in any of its units, eg `Meter` and `Milli<Meter>` for a total in
+/// meters. Add to it with `+=`, `extend` or `collect`.
+#[derive(Clone, Debug)]
+pub struct Total<B> where B: Unit {
+  value: B::Storage,
+}
+
+impl<B> Total<B> where B: Unit {
+  /// A total of zero.
+  pub fn new() -> Self {
+    Total {
+      value: storage::zero(),
+    }
+  }
+  /// The total in the base unit.
+  pub fn base(self) -> B {
+    B::new(self.value)
+  }
+}
+
+impl<B> Default for Total<B> where B: Unit {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<B,U> AddAssign<U> for Total<B> where U: IntoBase<B>, B: Unit {
+  fn add_assign(&mut self, value: U) {
+    self.value = storage::take(&mut self.value) + value.base().value();
+  }
+}
+
+impl<B,U> Extend<U> for Total<B> where U: IntoBase<B>, B: Unit {
+  fn extend<I>(&mut self, values: I) where I: IntoIterator<Item=U> {
+    for value in values {
+      *self += value;
+    }
+  }
+}
+
+impl<B,U> FromIterator<U> for Total<B> where U: IntoBase<B>, B: Unit {
+  fn from_iter<I>(values: I) -> Self where I: IntoIterator<Item=U> {
+    let mut total = Self::new();
+    total.extend(values);
+    total
+  }
+}
+
+/// Sum values in any one unit of the base into the base, eg millimeters into meters.
+pub fn total<B,I>(values: I) -> B where I: IntoIterator, I::Item: IntoBase<B>, B: Unit {
+  values.into_iter().collect::<Total<B>>().base()
+}
+
+#[test]
+fn totals_mixed_units() {
+  let mut total = Total::<Meter>::new();
+  total += Meter::from(2);
+  total += Kilo::<Meter>::from(1);
+  total.extend(vec![Milli::<Meter>::from(500), Milli::<Meter>::from(250)]);
+  assert_eq!(total.base(), Milli::<Meter>::from(1_002_750));
+}
+
+#[test]
+fn totals_in_the_base() {
+  let readings = [Milli::<Meter>::from(1_500), Milli::<Meter>::from(500)];
+  assert_eq!(total::<Meter,_>(readings.iter().cloned()), Meter::from(2));
+  assert_eq!(total::<Kilogram,_>(vec![Gram::from(1_500), Gram::from(500)]), Kilogram::from(2));
+  assert_eq!(Vec::<Meter>::new().into_iter().collect::<Total<Meter>>().base(), Meter::from(0));
+}
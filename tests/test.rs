@@ -1,7 +1,21 @@
-extern crate si;
+extern crate num;
+#[macro_use] extern crate si;
+
+use si::{Unit, IntoBase};
 
 use si::prefix::*;
 use si::base::*;
+use si::derived::*;
+use si::information::*;
+use si::temperature::*;
+use si::accepted::*;
+use si::parse::ParseError;
+use si::quantity::*;
+use si::total::{self, Total};
+use si::convert::{ConversionError, Rounding};
+use si::format::Engineering;
+use si::base::meter;
+use num::rational::BigRational;
 
 #[test]
 fn check_equivalences() {
@@ -15,12 +29,43 @@ fn check_equivalences() {
 fn check_addition() {
   assert!(Meter::from(1_000) + Kilo::<_>::from(1) == Kilo::<Meter>::from(2));
   assert!(Kilo::<Meter>::from(1) + Meter::from(1_000)  == Kilo::<Meter>::from(2));
+  assert!(Kilo::<Meter>::from(1) + Milli::<Meter>::from(500) == Milli::<Meter>::from(1_000_500));
 }
 
 #[test]
 fn check_subtraction() {
   assert!(Meter::from(2000) - Kilo::<_>::from(1) == Kilo::<Meter>::from(1));
   assert!(Kilo::<Meter>::from(2) - Meter::from(1_000)  == Kilo::<Meter>::from(1));
+  assert!(Mega::<Meter>::from(1) - Kilo::<Meter>::from(1) == Kilo::<Meter>::from(999));
+}
+
+#[test]
+fn check_prefix_kinds() {
+  assert_eq!(Kilo::<Meter>::kind(), PrefixKind::Kilo);
+  assert_eq!(PrefixKind::from_symbol("R").map(PrefixKind::name), Some("ronna"));
+  let symbols: Vec<_> = PrefixKind::all().iter().filter(|kind| !kind.is_binary()).map(|kind| kind.symbol()).take(3).collect();
+  assert_eq!(symbols, ["Q", "R", "Y"]);
+}
+
+#[test]
+fn check_conversion() {
+  assert!(Kilo::<Meter>::from(3).convert::<Milli<Meter>>() == Milli::<Meter>::from(3_000_000));
+  assert_eq!(Kibi::<Byte>::from(1).convert::<Kilo<Byte>>().value(), BigRational::new(128.into(), 125.into()));
+
+  // Integer storage compares in the smaller prefix, so nothing is truncated.
+  let kilometers = Kilo::<meter::Meter<i64>>::new(1);
+  assert!(kilometers != Milli::<meter::Meter<i64>>::new(1_000_001));
+  assert_eq!(kilometers.convert::<Milli<meter::Meter<i64>>>().value(), 1_000_000);
+
+  // A base and a smaller prefix are measured in the prefix, so only the result truncates.
+  let (millimeters, meter) = (Milli::<meter::Meter<i64>>::new(1_500), meter::Meter::<i64>::new(1));
+  assert!(millimeters != meter);
+  assert!(meter != millimeters);
+  assert!(millimeters > meter);
+  assert!(meter < millimeters);
+  assert_eq!((millimeters.clone() + meter.clone()).value(), 2_500);
+  assert_eq!((meter.clone() + millimeters.clone()).value(), 2);
+  assert_eq!((meter::Meter::<i64>::new(2) - millimeters).value(), 0);
 }
 
 #[test]
@@ -35,4 +80,326 @@ fn check_division() {
   assert!(Meter::from(10_000) / 10 == Kilo::<Meter>::from(1));
   assert!(Kilo::<Meter>::from(10) / 10  == Kilo::<Meter>::from(1));
   assert!(Kilo::<Meter>::from(10) / 10  == Meter::from(1_000));
-}
\ No newline at end of file
+}
+
+#[test]
+fn check_dimensions() {
+  let area: Area = Meter::from(2) * Meter::from(3);
+  assert!(area == Area::from(6));
+  assert!(Kilo::<Meter>::from(1) * Meter::from(1) == Area::from(1_000));
+  let velocity: Velocity = Meter::from(10) / Second::from(2);
+  assert!(velocity == Velocity::from(5));
+  let acceleration: Acceleration = velocity / Second::from(5);
+  assert!(acceleration == Acceleration::from(1));
+  let ratio: Dimensionless = Kilo::<Meter>::from(1) / Meter::from(10);
+  assert!(ratio == Dimensionless::from(100));
+  assert!(Area::from(6) / Meter::from(3) == Meter::from(2));
+}
+
+#[test]
+fn check_derived_units() {
+  let force: Force = Kilogram::from(2) * Meter::from(3) / (Second::from(1) * Second::from(1));
+  assert!(force == Newton::from(6));
+  assert!(Newton::from(force) * 2 == Newton::from(12));
+  let energy: Energy = Newton::from(2) * Meter::from(5);
+  assert!(energy == Joule::from(10));
+  let power: Power = Joule::from(10) / Second::from(2);
+  assert!(power == Watt::from(5));
+  let resistance: Resistance = Volt::from(12) / Ampere::from(4);
+  assert!(resistance == Ohm::from(3));
+  assert!(Dimensionless::from(50) / Second::from(1) == Hertz::from(50));
+  assert!(Kelvin::from(BigRational::new(27315.into(), 100.into())) == Kelvin::from(DegreeCelsius::from(0)));
+  assert!(DynQuantity::from(Kelvin::from(300)).to_unit::<CelsiusDifference>().is_err());
+  assert!(DynQuantity::from(CelsiusDifference::from(300)).to_unit::<Kelvin>().is_err());
+  assert!(CelsiusDifference::from(FahrenheitDifference::from(9)) * 20 == CelsiusDifference::from(100));
+  assert!(Kilo::<Newton>::from(2) * Meter::from(5) == Kilo::<Joule>::from(10));
+  assert!(Mega::<Watt>::from(1) == Watt::from(1_000_000));
+  assert!(Milli::<Litre>::from(250) + Litre::from(1) == Milli::<Litre>::from(1_250));
+}
+
+#[test]
+fn check_kilogram_is_base() {
+  assert!(Kilogram::from(1) == Gram::from(1_000));
+  assert!(Kilogram::from(1) == Milli::<Gram>::from(1_000_000));
+  assert!(Kilogram::from(1).quantity() == Mass::from(1));
+  assert!(Gram::from(1).quantity() * 1_000 == Mass::from(1));
+  assert!(IntoBase::<Kilogram>::base(Mega::<Gram>::from(1)) == Kilogram::from(1_000));
+  assert!(IntoBase::<Kilogram>::base(Gram::from(500)) * 2 == Kilogram::from(1));
+  assert_eq!(Kilogram::shortform(), "kg");
+  assert_eq!(Milli::<Gram>::shortform(), "mg");
+  assert_eq!(Micro::<Gram>::longform(), "microgram");
+  assert!(Kilogram::from(1) * Meter::from(1) / (Second::from(1) * Second::from(1)) == Newton::from(1));
+}
+
+#[test]
+fn check_display() {
+  let distance = Kilo::<Meter>::from(3) / 2;
+  assert_eq!(format!("{}", distance), "1.5 km");
+  assert_eq!(format!("{:#}", distance), "1.5 kilometers");
+  assert_eq!(format!("{:#}", Kilo::<Meter>::from(1)), "1 kilometer");
+  assert_eq!(format!("{:.3}", distance), "1.500 km");
+  assert_eq!(format!("{}", Meter::from(1) / 3), "1/3 m");
+  assert_eq!(format!("{:.2}", Meter::from(-2) / 3), "-0.67 m");
+  assert_eq!(format!("{:e}", Meter::from(1_500)), "1.5e3 m");
+  assert_eq!(format!("{:.2e}", Milli::<Second>::from(1) / 8), "1.25e-1 ms");
+  assert_eq!(format!("{:E}", Meter::from(15_000)), "1.5E4 m");
+  assert_eq!(format!("{:.1E}", Milli::<Second>::from(-1) / 8), "-1.2E-1 ms");
+  assert_eq!(format!("{}", Engineering(&Meter::from(15_000))), "15e3 m");
+  assert_eq!(format!("{:#}", Engineering(&Joule::from(1_500_000))), "1.5e6 joules");
+  assert_eq!(format!("{:>8}", Meter::from(5)), "     5 m");
+  assert_eq!(format!("{:#}", DegreeCelsius::from(20)), "20 degrees Celsius");
+  assert_eq!(format!("{}", Ohm::from(10)), "10 Ω");
+  assert_eq!(format!("{}", normalize(Meter::from(1_234_567), &Normalization::Engineering)), "1.234567 Mm");
+  assert_eq!(format!("{:.1}", normalize(Milli::<Second>::from(1_500), &Normalization::All)), "1.5 s");
+}
+
+#[test]
+fn check_parsing() {
+  assert_eq!("1500 m".parse::<Meter>(), Ok(Meter::from(1_500)));
+  assert_eq!("1.5km".parse::<Meter>(), Ok(Meter::from(1_500)));
+  assert_eq!("1500 m".parse::<Kilo<Meter>>(), Ok(Kilo::<Meter>::from(3) / 2));
+  assert_eq!("3/4 mm".parse::<Milli<Meter>>(), Ok(Milli::<Meter>::from(3) / 4));
+  assert_eq!("2e-6 s".parse::<Micro<Second>>(), Ok(Micro::<Second>::from(2)));
+  assert_eq!("250 µg".parse::<Kilogram>(), Ok(Kilogram::from(1) / 4_000_000));
+  assert_eq!("2 kilometers".parse::<Meter>(), Ok(Meter::from(2_000)));
+  assert_eq!("12 V".parse::<Volt>(), Ok(Volt::from(12)));
+  assert_eq!("2 Qm".parse::<Ronna<Meter>>(), Ok(Ronna::<Meter>::from(2_000)));
+  assert_eq!("1 quectogram".parse::<Ronto<Gram>>(), Ok(Ronto::<Gram>::from(1) / 1_000));
+  assert_eq!("1 s".parse::<Meter>(), Err(ParseError::UnknownUnit { found: "s".into(), expected: "m".into() }));
+  assert_eq!("1".parse::<Meter>(), Err(ParseError::MissingUnit { expected: "m".into() }));
+  assert_eq!("km".parse::<Meter>(), Err(ParseError::InvalidNumber("km".into())));
+  assert_eq!("1 s".parse::<Meter>().unwrap_err().to_string(), "`s` is not a unit of `m`");
+}
+
+#[test]
+fn check_ordering() {
+  use std::collections::BTreeMap;
+  assert!(Kilo::<Meter>::from(1) > Meter::from(999));
+  assert!(Meter::from(999) < Kilo::<Meter>::from(1));
+  assert!(Milli::<Meter>::from(1) < Micro::<Meter>::from(1_001));
+  let mut distances = vec![Meter::from(3), Meter::from(1), Meter::from(2)];
+  distances.sort();
+  assert_eq!(distances, vec![Meter::from(1), Meter::from(2), Meter::from(3)]);
+  assert_eq!(distances.iter().max(), Some(&Meter::from(3)));
+  assert_eq!(Kilo::<Meter>::from(5).clamp(Kilo::<Meter>::from(1), Kilo::<Meter>::from(2)), Kilo::<Meter>::from(2));
+  let mut map = BTreeMap::new();
+  map.insert(Second::from(2), "two");
+  map.insert(Second::from(1), "one");
+  assert_eq!(map.values().collect::<Vec<_>>(), vec![&"one", &"two"]);
+  assert!(Newton::from(2) > Newton::from(1));
+  assert!(Area::from(2) > Meter::from(1) * Meter::from(1));
+}
+
+#[test]
+fn check_storage() {
+  use num::rational::Ratio;
+  use si::base::second;
+
+  let distance = meter::Meter::<f64>::new(1_500.0);
+  assert_eq!(Kilo::<meter::Meter<f64>>::from(distance.clone()).value(), 1.5);
+  assert_eq!((distance / second::Second::<f64>::new(2.0)).value(), 750.0);
+  assert_eq!(format!("{}", meter::Meter::<f64>::new(0.1)), "0.1 m");
+  assert_eq!(meter::Meter::<f64>::new(2.0) * 3.0, meter::Meter::new(6.0));
+  assert_eq!(2.0 * Kilo::<meter::Meter<f32>>::new(1.5) / 4.0, Kilo::new(0.75));
+  assert_eq!(meter::Meter::<f64>::new(3.0) / 2, meter::Meter::new(1.5));
+  assert_eq!("1.5 km".parse::<meter::Meter<f32>>(), Ok(meter::Meter::new(1_500.0)));
+
+  // Integers truncate towards zero.
+  let millimeters = Milli::<meter::Meter<i64>>::new(2_500);
+  assert_eq!(millimeters.base().value(), 2);
+
+  let third = meter::Meter::<Ratio<i64>>::new(Ratio::new(1, 3));
+  assert_eq!((third.clone() + third.clone() + third).value(), Ratio::from_integer(1));
+  assert_eq!(meter::Meter::<Ratio<i64>>::new(Ratio::new(1, 3)) * Ratio::from_integer(3), meter::Meter::new(Ratio::from_integer(1)));
+  assert_eq!(Milli::<meter::Meter<Ratio<i64>>>::new(Ratio::new(1, 3)).base().value(), Ratio::new(1, 3_000));
+}
+
+#[test]
+fn check_information() {
+  assert_eq!(Kibi::<Byte>::from(1), Byte::from(1_024));
+  assert!(Gibi::<Byte>::from(1) > Giga::<Byte>::from(1));
+  assert_eq!(IntoBase::<Byte>::base(Kilo::<Bit>::from(8).base()), Kilo::<Byte>::from(1));
+  assert_eq!(human_size(Mebi::<Byte>::from(3) / 2, Prefixes::Binary).to_string(), "1.5 MiB");
+  assert_eq!(human_size(Byte::from(2_500_000), Prefixes::Best).to_string(), "2.5 MB");
+}
+
+#[test]
+fn check_dynamic_quantities() {
+  let distance = DynQuantity::from(Kilo::<Meter>::from(3));
+  let time = DynQuantity::from(Second::from(60));
+  assert!(distance.checked_add(&time).is_err());
+  let speed = distance / time;
+  assert_eq!(speed.to_unit::<Velocity>(), Ok(Velocity::from(50)));
+  assert!(speed.to_unit::<Meter>().is_err());
+}
+
+#[test]
+fn check_temperature() {
+  assert_eq!(DegreeCelsius::from(20) + CelsiusDifference::from(5), DegreeCelsius::from(25));
+  assert_eq!(DegreeCelsius::from(20) + KelvinDifference::from(300), DegreeCelsius::from(320));
+  assert_eq!(DegreeCelsius::from(20) - DegreeCelsius::from(5), CelsiusDifference::from(15));
+  assert_eq!(DegreeFahrenheit::from(DegreeCelsius::from(37)), DegreeFahrenheit::from(BigRational::new(493.into(), 5.into())));
+  assert_eq!(DegreeRankine::from(Kelvin::from(5)), DegreeRankine::from(9));
+  assert!(DegreeCelsius::from(-1) < DegreeCelsius::from(0));
+  assert_eq!("98.6 °F".parse::<DegreeFahrenheit>(), Ok(DegreeFahrenheit::from(BigRational::new(493.into(), 5.into()))));
+  assert_eq!(format!("{}", DegreeCelsius::from(20) - DegreeCelsius::from(25)), "-5 Δ°C");
+}
+
+#[test]
+fn check_accepted_units() {
+  assert_eq!(IntoBase::<Second>::base("2 h".parse::<Hour>().unwrap()), Second::from(7_200));
+  assert_eq!(Milli::<Litre>::from(250).quantity(), Volume::from(1) / 4_000);
+  assert_eq!(Kilo::<Tonne>::from(2).quantity(), Mass::from(2_000_000));
+  let flow: Quantity<_> = Litre::from(3_600) / Hour::from(1);
+  assert_eq!(flow, Volume::from(1) / Second::from(1_000));
+}
+
+#[test]
+fn check_operators() {
+  let (a, b) = (Meter::from(2), Kilo::<Meter>::from(1));
+  assert_eq!(&a + &a, Meter::from(4));
+  assert_eq!(&b + &b - a.clone(), Meter::from(1_998));
+  assert_eq!(-&a, Meter::from(-2));
+  assert_eq!(&a * 3, 3 * a.clone());
+  assert_eq!(BigRational::new(1.into(), 2.into()) * b.clone(), Meter::from(500));
+  let area: Area = &a * Meter::from(5);
+  assert_eq!(area, Area::from(10));
+  let mut total = Milli::<Meter>::from(0);
+  for length in &[Milli::<Meter>::from(2_000), Milli::<Meter>::from(3_000)] {
+    total += length;
+  }
+  total -= Milli::<Meter>::from(1);
+  total *= 2;
+  assert_eq!(total, Milli::<Meter>::from(9_998));
+  let mut speed = Meter::from(10);
+  speed /= 4;
+  assert_eq!(speed, Meter::from(5) / 2);
+}
+
+#[test]
+fn check_sums() {
+  let lengths = vec![Kilo::<Meter>::from(1), Kilo::<Meter>::from(3) / 2];
+  assert_eq!(lengths.iter().sum::<Kilo<Meter>>(), Kilo::<Meter>::from(5) / 2);
+  assert_eq!(lengths.into_iter().sum::<Kilo<Meter>>(), Meter::from(2_500));
+  let mut total = vec![Meter::from(1), Meter::from(2)].into_iter().collect::<Total<Meter>>();
+  total.extend(vec![Milli::<Meter>::from(500)]);
+  total += Kilo::<Meter>::from(1);
+  assert_eq!(total.base(), Meter::from(2_007) / 2);
+  let millis: Meter = total::total(vec![Milli::<Meter>::from(250); 4]);
+  assert_eq!(millis, Meter::from(1));
+}
+
+#[test]
+fn check_products() {
+  let scales = vec![Dimensionless::from(3) / 2, Meter::from(4) / Kilo::<Meter>::from(1)];
+  assert_eq!(Kilo::<Meter>::from(2) * scales.iter().product::<Dimensionless>(), Meter::from(12));
+  assert_eq!(scales.into_iter().product::<Dimensionless>(), Dimensionless::from(3) / 500);
+}
+
+#[test]
+fn check_primitive_conversions() {
+  use std::convert::TryFrom;
+  let length = Meter::from(12_345) / 10_000i64;
+  assert_eq!(length.to_f64(), Some(1.2345));
+  assert_eq!(Milli::<Meter>::from(length.clone()).round(Rounding::HalfEven), Milli::<Meter>::from(1_234));
+  assert_eq!(Milli::<Meter>::from(length.clone()).round(Rounding::Ceil), Milli::<Meter>::from(1_235));
+  assert_eq!(length.to_integer::<u8>(Rounding::TowardZero), Ok(1));
+  assert_eq!(i64::try_from(length), Err(ConversionError::NotIntegral));
+  assert_eq!(u16::try_from(Kilo::<Meter>::from(65)), Ok(65));
+  assert_eq!(i8::try_from(DegreeCelsius::from(-40)), Ok(-40));
+  assert_eq!(u8::try_from(Newton::from(256)), Err(ConversionError::OutOfRange));
+  assert_eq!(Second::try_from(0.5), Ok(Second::from(1) / 2i64));
+  assert_eq!(DegreeCelsius::try_from(-0.5f32), Ok(DegreeCelsius::from(BigRational::new((-1).into(), 2.into()))));
+  assert!(Newton::try_from(f64::NAN).is_err());
+  assert_eq!(Gram::from_decimal("0.3"), Ok(Gram::from(3) / 10i64));
+  assert_ne!(Gram::try_from(0.3).unwrap(), Gram::from(3) / 10i64);
+}
+
+#[test]
+fn check_bounded_values() {
+  let mut length = Meter::from(1);
+  for _ in 0..20 {
+    length = length * 7i64 / 3i64 + Milli::<Meter>::from(1);
+  }
+  let approximate = length.clone().approximate(1_000);
+  assert!(*approximate.value_ref().denom() <= 1_000.into());
+  let error = approximate - length.clone();
+  assert!(error.clone() < Meter::from(1) / 100_000i64 && -error < Meter::from(1) / 100_000i64);
+  assert_eq!(length.clone().round_to(Meter::from(1_000)), Meter::from(22_901_000));
+  assert_eq!(length.limit_significant_digits(4), Meter::from(22_900_000));
+}
+
+#[test]
+fn check_constants() {
+  use si::constants::*;
+  let photon = Joule::from(planck_constant() * hyperfine_transition_frequency());
+  assert_eq!(photon, Joule::from_decimal("6.09110229711386655e-24").unwrap());
+  assert_eq!(Coulomb::from(faraday_constant() * Mole::from(1)), Coulomb::from(elementary_charge() * avogadro_constant() * Mole::from(1)));
+  let rest_energy = Joule::from(electron_mass().value * speed_of_light() * speed_of_light());
+  assert!(rest_energy > Joule::from(8) / 100_000_000_000_000i64 && rest_energy < Joule::from(9) / 100_000_000_000_000i64);
+}
+
+// Units defined outside the crate, one of a dimension of its own and one of length.
+mod custom {
+  use si::Unit;
+
+  pub trait Viscosity: Unit {}
+
+  base_unit! {
+    name      = Poise,
+    longform  = poise,
+    shortform = P,
+    dimension = Viscosity,
+    exponents = (N1, P1, N1, Z0, Z0, Z0, Z0),
+    scale     = -1,
+    doc       = "A poise is a unit measuring dynamic viscosity, equal to 0.1 Pa·s.",
+  }
+
+  base_unit! {
+    name      = Furlong,
+    longform  = furlong,
+    shortform = fur,
+    dimension = ::si::dimension::Length,
+    quantity  = Length,
+    factor    = (201_168, 1_000),
+    doc       = "A furlong is a unit measuring distance, equal to 201.168 m.",
+  }
+}
+
+#[test]
+fn check_custom_units() {
+  use custom::*;
+  use std::convert::TryFrom;
+  fn viscous<P>(_: P) -> bool where P: custom::Viscosity { true }
+  assert!(viscous(Poise::from(1)));
+  assert_eq!(Poise::from(2) + Centi::<Poise>::from(50), Poise::from(5) / 2);
+  assert_eq!(Centi::<Poise>::from(2).convert::<Milli<Poise>>(), Milli::<Poise>::from(20));
+  assert_eq!(Poise::from(10) / Pascal::from(1), Second::from(1));
+  assert_eq!(Poise::from(Pascal::from(3) * Second::from(1)), Poise::from(30));
+  assert!(DynQuantity::from(Poise::from(1)).to_unit::<Radian>().is_err());
+  assert_eq!("3/2 cP".parse::<Poise>(), Ok(Poise::from(3) / 200));
+  assert_eq!(format!("{:#}", Centi::<Poise>::from(2)), "2 centipoises");
+  assert_eq!(Furlong::from(Length::from(Meter::from(201_168)) / 1_000), Furlong::from(1));
+  assert_eq!(Meter::from(Furlong::from(5).quantity()), Meter::from(1_005_840) / 1_000);
+  assert!("1 furlong".parse::<Furlong>().is_ok());
+  assert_eq!(u32::try_from(Furlong::from(8)), Ok(8));
+  assert_eq!(Furlong::try_from(0.5), Ok(Furlong::from(1) / 2i64));
+}
+
+#[test]
+fn check_registry() {
+  use custom::*;
+  use si::registry::{self, LookupError, Match};
+  use si::prefix::PrefixKind;
+  registry::register_prefixable::<Poise>();
+  registry::register::<Furlong>();
+  registry::alias("poise", "Po").unwrap();
+  let found = registry::lookup("centipoises").unwrap();
+  assert_eq!((found.prefix, found.shortform()), (Some(PrefixKind::Centi), "cP".to_string()));
+  assert_eq!(registry::lookup("centiPo").unwrap().quantity(BigRational::from_integer(2.into())).to_unit::<Poise>(), Ok(Poise::from(2) / 100));
+  assert_eq!(registry::lookup("fur").unwrap().factor(), BigRational::new(25_146.into(), 125.into()));
+  assert!(registry::lookup("kfur").is_err());
+  assert_eq!(registry::alias("parsec", "pc"), Err(LookupError::Unknown("parsec".into())));
+  assert!(registry::find("T").contains(&Match::Prefix(PrefixKind::Tera)));
+  assert!(registry::units().iter().any(|unit| unit.longform == "poise" && unit.prefixable));
+}